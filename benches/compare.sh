@@ -0,0 +1,33 @@
+#!/bin/sh
+# 比较指定版本与当前工作区的转换性能：benches/compare.sh <版本>
+#
+# 在临时 worktree 中用当前的 benches 测量 <版本> 的源码并保存为 before 基准，再测量当前工作区并与之比较。
+# 旧版本缺少 rlib 输出、criterion 依赖或 bench 配置时自动补上；迁移到 VisitMut 之前的版本改用 fold_with 转换。
+# 两次测量共用同一个 target 目录，criterion 的基准保存在 target/criterion 中；两个版本的包名相同，
+# cargo 会把另一份源码的构建产物当作最新的，因此每次测量前都清除本包的产物。
+set -e
+
+rev=${1:?usage: benches/compare.sh <rev>}
+root=$(git rev-parse --show-toplevel)
+dir=$(mktemp -d)
+git -C "$root" worktree add --detach "$dir" "$rev" >/dev/null
+trap 'git -C "$root" worktree remove --force "$dir"' EXIT
+
+rm -rf "$dir/benches"
+cp -r "$root/benches" "$dir/benches"
+cd "$dir"
+# Cargo.lock 不在版本库中，沿用当前的依赖版本
+[ -f "$root/Cargo.lock" ] && cp "$root/Cargo.lock" .
+grep -q '"rlib"' Cargo.toml || sed -i 's/^crate-type = \["cdylib"\]$/crate-type = ["cdylib", "rlib"]/' Cargo.toml
+grep -q '^criterion' Cargo.toml || sed -i 's/^\[dev-dependencies\]$/[dev-dependencies]\ncriterion = "0.5"/' Cargo.toml
+grep -q '^\[\[bench\]\]' Cargo.toml || printf '\n[[bench]]\nname = "transform"\nharness = false\n' >> Cargo.toml
+if grep -q '^impl Fold for JsxCssModulesVisitor' src/visitor.rs; then
+    sed -i 's/VisitMutWith/FoldWith/; s/module\.visit_mut_with(visitor);/let module = module.fold_with(visitor);/' benches/transform.rs
+fi
+
+export CARGO_TARGET_DIR="$root/target"
+cargo clean --release -p swc-plugin-jsx-css-modules
+cargo bench --bench transform -- --save-baseline before
+cd "$root"
+cargo clean --release -p swc-plugin-jsx-css-modules
+cargo bench --bench transform -- --baseline before
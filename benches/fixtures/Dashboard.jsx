@@ -0,0 +1,41 @@
+import React, { useState } from 'react';
+import cx from 'classnames';
+import './Dashboard.module.scss';
+import card from './Card.module.css';
+
+export default function Dashboard({ items, theme, onSelect }) {
+  const [active, setActive] = useState(null);
+
+  return (
+    <section className="dashboard :global(page)">
+      <header className={`header ${theme}`}>
+        <h1 className="title">Dashboard</h1>
+        <nav className={active ? 'nav nav-active' : 'nav'}>
+          {items.map(item => (
+            <a
+              key={item.id}
+              className={cx('nav-item', { selected: item.id === active })}
+              onClick={() => setActive(item.id)}
+            >
+              {item.label}
+            </a>
+          ))}
+        </nav>
+      </header>
+      <main className="content">
+        {items.map(item => (
+          <article key={item.id} className=":card(card) item">
+            <h2 className="item-title">{item.title}</h2>
+            <p className={item.description ? 'item-description' : undefined}>{item.description}</p>
+            <button className="button :global(btn btn-primary)" onClick={() => onSelect(item)}>
+              Open
+            </button>
+          </article>
+        ))}
+      </main>
+      <footer className="footer">
+        <span className={theme === 'dark' && 'footer-dark'}>© 2024</span>
+      </footer>
+    </section>
+  );
+}
@@ -0,0 +1,93 @@
+//! 转换性能基准：`cargo bench`
+//!
+//! 对 `benches/fixtures` 下的每个文件分别测量原始大小与重复拼接成大文件后的转换耗时，
+//! 解析与克隆 AST 不计入耗时。比较两次改动时先在旧版本上运行
+//! `cargo bench -- --save-baseline before`，再在新版本上运行 `cargo bench -- --baseline before`；
+//! 与没有该基准的旧版本比较时使用 `benches/compare.sh <版本>`。
+
+use std::{fs, path::Path};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+    ecma::{
+        ast::*,
+        parser::{parse_file_as_module, EsSyntax, Syntax},
+        visit::VisitMutWith,
+    },
+};
+use swc_plugin_jsx_css_modules::{Config, JsxCssModulesVisitor};
+
+// 模拟大型生成文件时重复拼接的次数
+const REPEAT: usize = 200;
+
+fn parse(src: &str) -> Module {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(Lrc::new(FileName::Anon), src.to_string());
+    let syntax = Syntax::Es(EsSyntax {
+        jsx: true,
+        ..Default::default()
+    });
+    parse_file_as_module(&fm, syntax, EsVersion::latest(), None, &mut vec![]).unwrap()
+}
+
+// 只保留第一份的导入，其余部分重复拼接，得到一个包含大量 JSX 的模块
+fn repeat_module(src: &str, times: usize) -> String {
+    let (imports, body): (Vec<&str>, Vec<&str>) = src.lines().partition(|line| line.starts_with("import "));
+    let body = body.join("\n").replace("export default ", "");
+    let mut output = imports.join("\n");
+    for i in 0..times {
+        output.push('\n');
+        output.push_str(&body.replace("function Dashboard", &format!("function Dashboard{}", i)));
+    }
+    output
+}
+
+// 在原 AST 上转换。`benches/compare.sh` 比较迁移到 VisitMut 之前的版本时会将这里替换为 fold_with
+fn transform(mut module: Module, visitor: &mut JsxCssModulesVisitor) -> Module {
+    module.visit_mut_with(visitor);
+    module
+}
+
+fn bench_transform(c: &mut Criterion) {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/fixtures");
+    let mut entries: Vec<_> = fs::read_dir(&fixtures).unwrap().flatten().map(|entry| entry.path()).collect();
+    entries.sort();
+
+    let configs = [
+        ("runtime", Config::default()),
+        (
+            "staticResolve",
+            Config {
+                static_resolve: true,
+                ..Default::default()
+            },
+        ),
+    ];
+
+    GLOBALS.set(&Default::default(), || {
+        for path in entries.iter().filter(|path| path.extension().is_some_and(|ext| ext == "jsx")) {
+            let src = fs::read_to_string(path).unwrap();
+            let modules = [
+                ("x1".to_string(), parse(&src)),
+                (format!("x{}", REPEAT), parse(&repeat_module(&src, REPEAT))),
+            ];
+
+            let mut group = c.benchmark_group(path.file_name().unwrap().to_string_lossy());
+            for (config_name, config) in &configs {
+                for (size, module) in &modules {
+                    group.bench_with_input(BenchmarkId::new(*config_name, size), module, |b, module| {
+                        b.iter_batched(
+                            || (module.clone(), JsxCssModulesVisitor::new(config.clone())),
+                            |(module, mut visitor)| transform(module, &mut visitor),
+                            BatchSize::LargeInput,
+                        )
+                    });
+                }
+            }
+            group.finish();
+        }
+    });
+}
+
+criterion_group!(benches, bench_transform);
+criterion_main!(benches);
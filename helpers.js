@@ -5,10 +5,17 @@
 }(this, (function (exports) { 'use strict';
 
   var splitString = function splitString(string) {
-    return string.trim().split(' ');
+    return string.trim().split(' ').filter(Boolean);
   };
 
-  var getMatcher = function getMatcher(styles, prefer) {
+  var getMatcher = function getMatcher(styles, prefer, scopes) {
+    var scopePatterns = Object.keys(scopes || {}).map(function (name) {
+      return {
+        name: name,
+        pattern: new RegExp(':' + name.replace(/\$/g, '\\$') + '\\([\\s\\S]*?\\)', 'g')
+      };
+    });
+
     return function (classNames) {
       if (typeof classNames !== 'string') {
         return null;
@@ -16,15 +23,26 @@
 
       var globalClassNames = [];
       var localClassNames = [];
-      var restClassNames = splitString(classNames.replace(/\s{2,}/g, ' ').replace(/:global\([\s\S]*?\)/g, function (text) {
+      var scopedClassNames = [];
+      var restString = classNames.replace(/\s{2,}/g, ' ').replace(/:global\([\s\S]*?\)/g, function (text) {
         globalClassNames = globalClassNames.concat(splitString(text.replace(/(:global\(|\))/g, '')));
         return '';
       }).replace(/:local\([\s\S]*?\)/g, function (text) {
         localClassNames = localClassNames.concat(splitString(text.replace(/(:local\(|\))/g, '')));
         return '';
-      }));
-
-      if (prefer === 'local') {
+      });
+      scopePatterns.forEach(function (scope) {
+        var name = scope.name;
+        restString = restString.replace(scope.pattern, function (text) {
+          splitString(text.slice(name.length + 2, -1)).forEach(function (className) {
+            scopedClassNames.push(scopes[name][className] || className);
+          });
+          return '';
+        });
+      });
+      var restClassNames = splitString(restString);
+
+      if (prefer === 'local' || prefer === 'strict-local') {
         localClassNames = localClassNames.concat(restClassNames);
       } else {
         globalClassNames = globalClassNames.concat(restClassNames);
@@ -32,7 +50,7 @@
 
       return localClassNames.map(function (className) {
         return styles[className] || className;
-      }).concat(globalClassNames).join(' ').trim();
+      }).concat(scopedClassNames).concat(globalClassNames).join(' ').trim();
     };
   };
 
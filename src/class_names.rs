@@ -0,0 +1,65 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::Prefer;
+
+static WHITESPACE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s{2,}").unwrap());
+
+/// 拆分后的类名，`locals` 需要经过 CSS Modules 映射，`globals` 原样输出，
+/// `scoped` 为 `:name(...)` 中只在指定样式文件中映射的 (作用域, 类名)
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ClassNames {
+    pub locals: Vec<String>,
+    pub scoped: Vec<(String, String)>,
+    pub globals: Vec<String>,
+}
+
+fn split_string(string: &str) -> Vec<String> {
+    string
+        .split(' ')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+// 等价于按 `(?s):keyword\(.*?\)` 逐个替换，每个类名都会调用，因此不为关键字单独编译正则
+fn extract(input: &str, keyword: &str, collected: &mut Vec<String>) -> String {
+    let marker = format!(":{}(", keyword);
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find(&marker) {
+        let Some(end) = rest[start + marker.len()..].find(')') else {
+            break;
+        };
+        let inner = &rest[start + marker.len()..start + marker.len() + end];
+        collected.extend(split_string(&inner.replace(&marker, "")));
+        output.push_str(&rest[..start]);
+        rest = &rest[start + marker.len() + end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// 与 helpers.js 中 `getMatcher` 保持一致的类名解析逻辑，`scopes` 的顺序决定 `scoped` 的顺序
+pub fn parse_class_names(class_names: &str, prefer: Prefer, scopes: &[&str]) -> ClassNames {
+    let mut result = ClassNames::default();
+    let collapsed = WHITESPACE.replace_all(class_names, " ");
+    let rest = extract(&collapsed, "global", &mut result.globals);
+    let mut rest = extract(&rest, "local", &mut result.locals);
+    for scope in scopes {
+        let mut class_names = vec![];
+        rest = extract(&rest, scope, &mut class_names);
+        result
+            .scoped
+            .extend(class_names.into_iter().map(|class_name| (scope.to_string(), class_name)));
+    }
+    let rest = split_string(&rest);
+
+    if prefer != Prefer::Global {
+        result.locals.extend(rest);
+    } else {
+        result.globals.extend(rest);
+    }
+    result
+}
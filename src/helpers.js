@@ -1,16 +1,23 @@
 function splitString(string) {
-  return string.trim().split(' ');
+  return string.trim().split(' ').filter(Boolean);
 }
 
-export function getMatcher(styles, prefer) {
-  if (typeof classNames !== 'string') {
-    return null;
-  }
+export function getMatcher(styles, prefer, scopes) {
+  // :name(...) 的正则只在创建 matcher 时构建一次
+  const scopePatterns = Object.keys(scopes || {}).map(name => ({
+    name,
+    pattern: new RegExp(`:${name.replace(/\$/g, '\\$')}\\([\\s\\S]*?\\)`, 'g'),
+  }));
 
-  let globalClassNames = [];
-  let localClassNames = [];
-  let restClassNames = splitString(
-    classNames
+  return classNames => {
+    if (typeof classNames !== 'string') {
+      return null;
+    }
+
+    let globalClassNames = [];
+    let localClassNames = [];
+    const scopedClassNames = [];
+    let restString = classNames
       .replace(/\s{2,}/g, ' ')
       .replace(/:global\([\s\S]*?\)/g, text => {
         globalClassNames = globalClassNames.concat(
@@ -23,18 +30,30 @@ export function getMatcher(styles, prefer) {
           splitString(text.replace(/(:local\(|\))/g, ''))
         );
         return '';
-      })
-  );
+      });
+
+    // :name(...) 中的类名只在对应的样式文件中映射
+    scopePatterns.forEach(({ name, pattern }) => {
+      restString = restString.replace(pattern, text => {
+        splitString(text.slice(name.length + 2, -1)).forEach(className => {
+          scopedClassNames.push(scopes[name][className] || className);
+        });
+        return '';
+      });
+    });
+    const restClassNames = splitString(restString);
 
-  if (prefer === 'local') {
-    localClassNames = localClassNames.concat(restClassNames);
-  } else {
-    globalClassNames = globalClassNames.concat(restClassNames);
-  }
+    if (prefer === 'local' || prefer === 'strict-local') {
+      localClassNames = localClassNames.concat(restClassNames);
+    } else {
+      globalClassNames = globalClassNames.concat(restClassNames);
+    }
 
-  return localClassNames
-    .map(className => styles[className] || className)
-    .concat(globalClassNames)    
-    .join(' ')
-    .trim();
+    return localClassNames
+      .map(className => styles[className] || className)
+      .concat(scopedClassNames)
+      .concat(globalClassNames)
+      .join(' ')
+      .trim();
+  };
 }
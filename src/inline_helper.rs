@@ -0,0 +1,76 @@
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, Span},
+    ecma::{
+        ast::*,
+        parser::{parse_file_as_script, Syntax},
+        visit::{VisitMut, VisitMutWith},
+    },
+};
+
+// 与 helpers.js 中 getMatcher 行为一致的精简实现，辅助函数都定义在内部以免污染模块作用域
+const INLINE_GET_MATCHER: &str = r#"
+function getMatcher(styles, prefer, scopes) {
+  var splitString = function (string) {
+    return string.trim().split(' ').filter(Boolean);
+  };
+  var scopePatterns = Object.keys(scopes || {}).map(function (name) {
+    return { name: name, pattern: new RegExp(':' + name.replace(/\$/g, '\\$') + '\\([\\s\\S]*?\\)', 'g') };
+  });
+  return function (classNames) {
+    if (typeof classNames !== 'string') {
+      return null;
+    }
+    var globalClassNames = [];
+    var localClassNames = [];
+    var scopedClassNames = [];
+    var restString = classNames.replace(/\s{2,}/g, ' ').replace(/:global\([\s\S]*?\)/g, function (text) {
+      globalClassNames = globalClassNames.concat(splitString(text.replace(/(:global\(|\))/g, '')));
+      return '';
+    }).replace(/:local\([\s\S]*?\)/g, function (text) {
+      localClassNames = localClassNames.concat(splitString(text.replace(/(:local\(|\))/g, '')));
+      return '';
+    });
+    scopePatterns.forEach(function (scope) {
+      restString = restString.replace(scope.pattern, function (text) {
+        splitString(text.slice(scope.name.length + 2, -1)).forEach(function (className) {
+          scopedClassNames.push(scopes[scope.name][className] || className);
+        });
+        return '';
+      });
+    });
+    var restClassNames = splitString(restString);
+    if (prefer === 'global') {
+      globalClassNames = globalClassNames.concat(restClassNames);
+    } else {
+      localClassNames = localClassNames.concat(restClassNames);
+    }
+    return localClassNames.map(function (className) {
+      return styles[className] || className;
+    }).concat(scopedClassNames).concat(globalClassNames).join(' ').trim();
+  };
+}
+"#;
+
+// 解析出的节点来自独立的 SourceMap，清空位置信息避免与当前文件的位置混淆
+struct SpanRemover;
+
+impl VisitMut for SpanRemover {
+    fn visit_mut_span(&mut self, span: &mut Span) {
+        *span = Span::default();
+    }
+}
+
+/// 创建内联的 `function getMatcher(styles, prefer, scopes) { ... }` 声明，函数名使用传入的绑定
+pub fn create_inline_get_matcher(ident: Ident) -> Stmt {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(Lrc::new(FileName::Anon), INLINE_GET_MATCHER.to_string());
+    let script = parse_file_as_script(&fm, Syntax::default(), EsVersion::Es5, None, &mut vec![])
+        .expect("failed to parse the inline getMatcher helper");
+
+    let mut stmt = script.body.into_iter().next().unwrap();
+    stmt.visit_mut_with(&mut SpanRemover);
+    if let Stmt::Decl(Decl::Fn(function)) = &mut stmt {
+        function.ident = ident;
+    }
+    stmt
+}
@@ -1,35 +1,395 @@
 use swc_core::{
     ecma::{
         ast::*,
-        visit::{FoldWith},
+        visit::VisitMutWith,
     },
+    common::plugin::metadata::TransformPluginMetadataContextKind,
     plugin::{plugin_transform, proxies::TransformPluginProgramMetadata},
 };
+use std::path::Path;
 use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// 未显式标记的类名按局部还是全局处理
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq)]
+#[serde(try_from = "String")]
+pub enum Prefer {
+    #[default]
+    Local,
+    Global,
+    /// 与 `Local` 相同，但字面量中的类名在样式文件中不存在时构建报错
+    StrictLocal,
+}
+
+impl Prefer {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Prefer::Local => "local",
+            Prefer::Global => "global",
+            Prefer::StrictLocal => "strict-local",
+        }
+    }
+}
+
+impl TryFrom<String> for Prefer {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        match value.as_str() {
+            "local" => Ok(Prefer::Local),
+            "global" => Ok(Prefer::Global),
+            "strict-local" => Ok(Prefer::StrictLocal),
+            _ => Err(format!(
+                r#"invalid `prefer` value "{}", expected "local", "global" or "strict-local""#,
+                value
+            )),
+        }
+    }
+}
+
+/// 为副作用样式导入补充绑定时使用的导入形式
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq)]
+#[serde(try_from = "String")]
+pub enum ImportStyle {
+    #[default]
+    Default,
+    Namespace,
+    /// 只按名称导入文件中静态引用到的类名，便于打包工具摇树
+    Named,
+}
+
+impl TryFrom<String> for ImportStyle {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        match value.as_str() {
+            "default" => Ok(ImportStyle::Default),
+            "namespace" => Ok(ImportStyle::Namespace),
+            "named" => Ok(ImportStyle::Named),
+            _ => Err(format!(
+                r#"invalid `importStyle` value "{}", expected "default", "namespace" or "named""#,
+                value
+            )),
+        }
+    }
+}
+
+/// 类名在样式文件中不存在时的处理方式
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq)]
+#[serde(try_from = "String")]
+pub enum MissingClass {
+    #[default]
+    Ignore,
+    Warn,
+    Error,
+}
+
+impl TryFrom<String> for MissingClass {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        match value.as_str() {
+            "ignore" => Ok(MissingClass::Ignore),
+            "warn" => Ok(MissingClass::Warn),
+            "error" => Ok(MissingClass::Error),
+            _ => Err(format!(
+                r#"invalid `missingClass` value "{}", expected "ignore", "warn" or "error""#,
+                value
+            )),
+        }
+    }
+}
+
+/// 目标框架，决定转换哪些类名属性以及属性值中的对象/数组如何处理
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq)]
+#[serde(try_from = "String")]
+pub enum Framework {
+    #[default]
+    React,
+    Preact,
+    Solid,
+    Vue,
+    Qwik,
+}
+
+/// 类名属性的处理方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClassAttribute {
+    /// 值为对象字面量时按 `{ 类名: 条件 }` 映射其中的键
+    pub objects: bool,
+    /// 值为数组字面量时逐个映射其中的元素
+    pub arrays: bool,
+    /// 是否同时转换组件上的该属性，否则只转换 DOM 元素
+    pub components: bool,
+}
+
+impl ClassAttribute {
+    /// 只接受字符串、在所有元素上转换，`attributes` 配置中的属性均按此处理
+    pub const STRING: Self = Self {
+        objects: false,
+        arrays: false,
+        components: true,
+    };
+    /// Solid 的 classList 由运行时直接作用于 DOM 元素，组件上只是普通的 prop
+    pub const CLASS_LIST: Self = Self {
+        objects: true,
+        arrays: false,
+        components: false,
+    };
+    /// Vue 与 Qwik 的 class 以及 Qwik 的 class:list 同时接受字符串、数组和对象
+    pub const CLASS_BINDING: Self = Self {
+        objects: true,
+        arrays: true,
+        components: true,
+    };
+}
+
+impl Framework {
+    /// 框架预设在 `attributes` 配置之外转换的属性
+    pub fn attributes(&self) -> &'static [(&'static str, ClassAttribute)] {
+        match self {
+            Framework::React => &[("className", ClassAttribute::STRING)],
+            Framework::Preact => &[("class", ClassAttribute::STRING), ("className", ClassAttribute::STRING)],
+            Framework::Solid => &[("class", ClassAttribute::STRING), ("classList", ClassAttribute::CLASS_LIST)],
+            Framework::Vue => &[("class", ClassAttribute::CLASS_BINDING)],
+            Framework::Qwik => &[("class", ClassAttribute::CLASS_BINDING), ("class:list", ClassAttribute::CLASS_BINDING)],
+        }
+    }
+}
+
+impl TryFrom<String> for Framework {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        match value.as_str() {
+            "react" => Ok(Framework::React),
+            "preact" => Ok(Framework::Preact),
+            "solid" => Ok(Framework::Solid),
+            "vue" => Ok(Framework::Vue),
+            "qwik" => Ok(Framework::Qwik),
+            _ => Err(format!(
+                r#"invalid `framework` value "{}", expected "react", "preact", "solid", "vue" or "qwik""#,
+                value
+            )),
+        }
+    }
+}
+
+/// 记录每个文件中类名使用情况的方式，供 CSS 清理与无用样式检测使用
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq)]
+#[serde(try_from = "String")]
+pub enum UsageManifest {
+    #[default]
+    None,
+    /// 导出 `__cssModulesUsage` 常量
+    Export,
+    /// 在文件开头写入 `/* @jsx-css-modules-usage {...} */` 注释
+    Comment,
+}
+
+impl TryFrom<String> for UsageManifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        match value.as_str() {
+            "none" => Ok(UsageManifest::None),
+            "export" => Ok(UsageManifest::Export),
+            "comment" => Ok(UsageManifest::Comment),
+            _ => Err(format!(
+                r#"invalid `usageManifest` value "{}", expected "none", "export" or "comment""#,
+                value
+            )),
+        }
+    }
+}
+
+/// 需要转换类名属性的 JSX 元素
+#[derive(Debug, Default, Deserialize, Clone, PartialEq)]
+#[serde(try_from = "Value")]
+pub enum Elements {
+    #[default]
+    All,
+    /// 只转换 DOM 元素（小写开头或命名空间标签），跳过所有组件
+    Intrinsic,
+    /// 按元素名（如 `div`、`Button`、`Foo.Bar`）筛选，`include` 为空时包含所有元素
+    Filter { include: Vec<String>, exclude: Vec<String> },
+}
+
+impl TryFrom<Value> for Elements {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, String> {
+        match &value {
+            Value::String(name) if name == "all" => Ok(Elements::All),
+            Value::String(name) if name == "intrinsic" => Ok(Elements::Intrinsic),
+            Value::Object(object) => {
+                check_object_fields("elements", &value, object, &["include", "exclude"])?;
+                let field = |field: &str| match object.get(field) {
+                    None => Ok(vec![]),
+                    Some(Value::Array(patterns)) if patterns.iter().all(Value::is_string) => Ok(patterns
+                        .iter()
+                        .filter_map(|pattern| pattern.as_str().map(String::from))
+                        .collect()),
+                    Some(_) => Err(format!(
+                        "invalid `elements` value {}: `{}` must be an array of strings",
+                        value, field
+                    )),
+                };
+                Ok(Elements::Filter {
+                    include: field("include")?,
+                    exclude: field("exclude")?,
+                })
+            }
+            _ => Err(format!(
+                r#"invalid `elements` value {}, expected "all", "intrinsic" or {{ "include": [...], "exclude": [...] }}"#,
+                value
+            )),
+        }
+    }
+}
+
+/// 运行时 `getMatcher` 的引入方式
+#[derive(Debug, Default, Deserialize, Clone, PartialEq)]
+#[serde(try_from = "Value")]
+pub enum Helper {
+    /// 从 `swc-plugin-jsx-css-modules/helpers` 导入
+    #[default]
+    Import,
+    /// 在文件中内联一份精简的 getMatcher 实现，不依赖运行时包
+    Inline,
+    /// 从自定义模块导入指定的导出
+    Custom { module: String, export: String },
+}
+
+impl TryFrom<Value> for Helper {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, String> {
+        match &value {
+            Value::String(name) if name == "import" => Ok(Helper::Import),
+            Value::String(name) if name == "inline" => Ok(Helper::Inline),
+            Value::Object(object) => {
+                check_object_fields("helper", &value, object, &["module", "export"])?;
+                let field = |field: &str| match object.get(field) {
+                    Some(Value::String(field)) => Ok(field.clone()),
+                    Some(_) => Err(format!("invalid `helper` value {}: `{}` must be a string", value, field)),
+                    None => Err(format!("invalid `helper` value {}: missing `{}`", value, field)),
+                };
+                Ok(Helper::Custom {
+                    module: field("module")?,
+                    export: field("export")?,
+                })
+            }
+            _ => Err(format!(
+                r#"invalid `helper` value {}, expected "import", "inline" or {{ "module": "...", "export": "..." }}"#,
+                value
+            )),
+        }
+    }
+}
+
+// 对象形式的配置项不接受未知字段，错误信息中带上配置项名与原始取值
+fn check_object_fields(key: &str, value: &Value, object: &Map<String, Value>, fields: &[&str]) -> Result<(), String> {
+    match object.keys().find(|field| !fields.contains(&field.as_str())) {
+        Some(field) => {
+            let expected: Vec<String> = fields.iter().map(|field| format!("`{}`", field)).collect();
+            Err(format!(
+                "invalid `{}` value {}: unknown field `{}`, expected {}",
+                key,
+                value,
+                field,
+                expected.join(" or ")
+            ))
+        }
+        None => Ok(()),
+    }
+}
 
 #[derive(Debug, Deserialize, Clone)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Config {
-    #[serde(default = "default_prefer")]
-    pub prefer: String,
-    #[serde(default = "default_style_file_reg")]
+    #[serde(default)]
+    pub prefer: Prefer,
+    #[serde(default)]
     pub style_file_reg: Vec<String>,
-    #[serde(default = "default_import_style")]
-    pub import_style: String,
+    #[serde(default)]
+    pub style_file_glob: Vec<String>,
+    #[serde(default)]
+    pub import_style: ImportStyle,
+    #[serde(default)]
+    pub static_resolve: bool,
+    #[serde(default)]
+    pub local_ident_name: Option<String>,
+    #[serde(default)]
+    pub local_ident_context: Option<String>,
+    #[serde(default)]
+    pub local_ident_hash_salt: Option<String>,
+    #[serde(default)]
+    pub missing_class: MissingClass,
+    #[serde(default = "default_attributes")]
+    pub attributes: Vec<String>,
+    #[serde(default = "default_class_name_helpers")]
+    pub class_name_helpers: Vec<String>,
+    #[serde(default)]
+    pub helper: Helper,
+    #[serde(default)]
+    pub usage_manifest: UsageManifest,
+    #[serde(default)]
+    pub framework: Framework,
+    #[serde(default)]
+    pub elements: Elements,
+    /// 兼容 babel-plugin-react-css-modules 的 `styleName` 写法
+    #[serde(default)]
+    pub style_name: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            prefer: Prefer::default(),
+            style_file_reg: vec![],
+            style_file_glob: vec![],
+            import_style: ImportStyle::default(),
+            static_resolve: false,
+            local_ident_name: None,
+            local_ident_context: None,
+            local_ident_hash_salt: None,
+            missing_class: MissingClass::default(),
+            attributes: default_attributes(),
+            class_name_helpers: default_class_name_helpers(),
+            helper: Helper::default(),
+            usage_manifest: UsageManifest::default(),
+            framework: Framework::default(),
+            elements: Elements::default(),
+            style_name: false,
+        }
+    }
 }
 
-fn default_prefer() -> String {
-    "local".to_string()
+impl Config {
+    /// 解析插件配置，未知字段和非法取值都会返回错误；空配置使用默认值
+    pub fn parse(json: &str) -> Result<Self, String> {
+        if json.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        serde_json::from_str(json).map_err(|err| err.to_string())
+    }
 }
 
-fn default_style_file_reg() -> Vec<String> {
-    vec![r"\.(css|scss|sass|less)$".to_string()]
+fn default_attributes() -> Vec<String> {
+    vec!["className".to_string()]
 }
 
-fn default_import_style() -> String {
-    "default".to_string()
+fn default_class_name_helpers() -> Vec<String> {
+    vec!["classnames".to_string(), "clsx".to_string()]
 }
 
+mod class_names;
+mod inline_helper;
+mod local_ident;
+mod pragma;
+mod stylesheet;
+mod usage;
 mod visitor;
 pub use visitor::JsxCssModulesVisitor;
 
@@ -37,12 +397,29 @@ pub use visitor::JsxCssModulesVisitor;
 mod tests;
 
 #[plugin_transform]
-pub fn transform_program(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
-    let config: Config = serde_json::from_str(&metadata.get_transform_plugin_config().unwrap_or_default()).unwrap_or_else(|_| Config {
-        prefer: default_prefer(),
-        style_file_reg: default_style_file_reg(),
-        import_style: default_import_style(),
-    });
-    let mut folder = JsxCssModulesVisitor::new(config);
-    program.fold_with(&mut folder)
+pub fn transform_program(mut program: Program, metadata: TransformPluginProgramMetadata) -> Program {
+    // 配置错误时直接中止转换，避免静默地输出错误的类名
+    let mut visitor = Config::parse(&metadata.get_transform_plugin_config().unwrap_or_default())
+        .and_then(JsxCssModulesVisitor::try_new)
+        .unwrap_or_else(|err| panic!("swc-plugin-jsx-css-modules: invalid config: {}", err));
+
+    // 插件运行在 wasm 沙箱中，宿主的工作目录被挂载到 /cwd
+    let cwd = metadata.get_context(&TransformPluginMetadataContextKind::Cwd);
+    if let Some(filename) = metadata.get_context(&TransformPluginMetadataContextKind::Filename) {
+        let filename = Path::new(&filename);
+        let relative = match &cwd {
+            Some(cwd) => filename.strip_prefix(cwd).unwrap_or(filename),
+            None => filename,
+        };
+        visitor = visitor
+            .with_filename(Path::new("/cwd").join(relative).to_string_lossy())
+            .with_cwd("/cwd");
+    }
+
+    if let Some(comments) = metadata.comments {
+        visitor = visitor.with_comments(comments);
+    }
+
+    program.visit_mut_with(&mut visitor);
+    program
 }
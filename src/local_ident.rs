@@ -0,0 +1,183 @@
+use std::{
+    path::{Component, Path},
+    sync::LazyLock,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use regex::Regex;
+
+static LEADING_DIGIT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^((-?[0-9])|--)").unwrap());
+static UNSAFE_CHARS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"[<>:"/\\|?*\x00-\x1f\x{80}-\x{9f}.]"#).unwrap());
+static HASH_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\[(?:([^:\]]+):)?(?:hash|contenthash|fullhash)(?::([a-z]+\d*))?(?::(\d+))?\]").unwrap()
+});
+static PATH_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\[(name|ext|path|folder)\]").unwrap());
+static LOCAL_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\[local\]").unwrap());
+
+// webpack 默认的 md4 哈希实现（RFC 1320）
+fn md4(input: &[u8]) -> [u8; 16] {
+    let mut message = input.to_vec();
+    let bit_len = (input.len() as u64).wrapping_mul(8);
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_len.to_le_bytes());
+
+    let mut state: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+    let f = |x: u32, y: u32, z: u32| (x & y) | (!x & z);
+    let g = |x: u32, y: u32, z: u32| (x & y) | (x & z) | (y & z);
+    let h = |x: u32, y: u32, z: u32| x ^ y ^ z;
+
+    for chunk in message.chunks(64) {
+        let x: Vec<u32> = chunk
+            .chunks(4)
+            .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+            .collect();
+        let [mut a, mut b, mut c, mut d] = state;
+
+        for &i in &[0, 4, 8, 12] {
+            a = a.wrapping_add(f(b, c, d)).wrapping_add(x[i]).rotate_left(3);
+            d = d.wrapping_add(f(a, b, c)).wrapping_add(x[i + 1]).rotate_left(7);
+            c = c.wrapping_add(f(d, a, b)).wrapping_add(x[i + 2]).rotate_left(11);
+            b = b.wrapping_add(f(c, d, a)).wrapping_add(x[i + 3]).rotate_left(19);
+        }
+        for &i in &[0, 1, 2, 3] {
+            a = a.wrapping_add(g(b, c, d)).wrapping_add(x[i]).wrapping_add(0x5a827999).rotate_left(3);
+            d = d.wrapping_add(g(a, b, c)).wrapping_add(x[i + 4]).wrapping_add(0x5a827999).rotate_left(5);
+            c = c.wrapping_add(g(d, a, b)).wrapping_add(x[i + 8]).wrapping_add(0x5a827999).rotate_left(9);
+            b = b.wrapping_add(g(c, d, a)).wrapping_add(x[i + 12]).wrapping_add(0x5a827999).rotate_left(13);
+        }
+        for &i in &[0, 2, 1, 3] {
+            a = a.wrapping_add(h(b, c, d)).wrapping_add(x[i]).wrapping_add(0x6ed9eba1).rotate_left(3);
+            d = d.wrapping_add(h(a, b, c)).wrapping_add(x[i + 8]).wrapping_add(0x6ed9eba1).rotate_left(9);
+            c = c.wrapping_add(h(d, a, b)).wrapping_add(x[i + 4]).wrapping_add(0x6ed9eba1).rotate_left(11);
+            b = b.wrapping_add(h(c, d, a)).wrapping_add(x[i + 12]).wrapping_add(0x6ed9eba1).rotate_left(15);
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+    }
+
+    let mut digest = [0u8; 16];
+    for (i, word) in state.iter().enumerate() {
+        digest[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+    }
+    digest
+}
+
+fn digest(bytes: &[u8], encoding: &str) -> String {
+    match encoding {
+        "base64" => STANDARD.encode(bytes),
+        _ => bytes.iter().map(|byte| format!("{:02x}", byte)).collect(),
+    }
+}
+
+// 解析 `.`、`..` 并统一使用 `/` 作为分隔符
+fn normalize_path(path: &Path) -> String {
+    let mut parts: Vec<String> = vec![];
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+            Component::ParentDir => {
+                if parts.last().is_some_and(|part| part != "..") {
+                    parts.pop();
+                } else {
+                    parts.push("..".to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    parts.join("/")
+}
+
+fn relative_path(context: &Path, path: &Path) -> String {
+    let context = normalize_path(context);
+    let path = normalize_path(path);
+    let context: Vec<&str> = context.split('/').filter(|part| !part.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+    let common = context.iter().zip(path.iter()).take_while(|(a, b)| a == b).count();
+
+    std::iter::repeat_n("..", context.len() - common)
+        .chain(path[common..].iter().copied())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn escape_local_ident(local_ident: &str) -> String {
+    let local_ident = LEADING_DIGIT.replace(local_ident, "_$1");
+    UNSAFE_CHARS.replace_all(&local_ident, "-").into_owned()
+}
+
+/// 按照 css-loader 的 `localIdentName` 规则生成类名，
+/// 支持 `[name]`、`[local]`、`[ext]`、`[path]`、`[folder]` 与 `[hash:<digest>:<length>]`
+pub fn generate_local_ident(
+    local_ident_name: &str,
+    context: &Path,
+    resource_path: &Path,
+    local_name: &str,
+    hash_salt: Option<&str>,
+) -> String {
+    let relative_resource_path = relative_path(context, resource_path);
+    let content = format!("{}\x00{}", relative_resource_path, local_name);
+
+    let mut hash_digest = "hex".to_string();
+    let mut hash_digest_length = 20;
+    if let Some(caps) = HASH_PATTERN.captures(local_ident_name) {
+        if let Some(encoding) = caps.get(2) {
+            hash_digest = encoding.as_str().to_string();
+        }
+        if let Some(length) = caps.get(3) {
+            hash_digest_length = length.as_str().parse().unwrap_or(hash_digest_length);
+        }
+    }
+
+    let mut local_ident_hash = String::new();
+    let mut tier: u32 = 0;
+    while local_ident_hash.len() < hash_digest_length {
+        let mut input = hash_salt.unwrap_or_default().as_bytes().to_vec();
+        input.extend_from_slice(&tier.to_le_bytes());
+        input.extend_from_slice(content.as_bytes());
+        let hash = format!("{}{}", local_ident_hash, digest(&md4(&input), &hash_digest));
+        let hash = hash.trim_start_matches(|c: char| c.is_ascii_digit()).replace('/', "_");
+        local_ident_hash = hash
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .take(hash_digest_length)
+            .collect();
+        tier += 1;
+    }
+
+    let file_name = resource_path.file_name().unwrap_or_default().to_string_lossy();
+    let ext = resource_path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let name = &file_name[..file_name.len() - ext.len()];
+    let directory = relative_resource_path
+        .rsplit_once('/')
+        .map(|(directory, _)| directory)
+        .unwrap_or_default();
+    let path = if directory.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", directory)
+    };
+    let folder = directory.rsplit('/').next().unwrap_or_default();
+
+    let result = HASH_PATTERN.replace_all(local_ident_name, local_ident_hash.as_str());
+    let result = PATH_PATTERN.replace_all(&result, |caps: &regex::Captures| match caps[1].to_lowercase().as_str() {
+            "name" => name.to_string(),
+            "ext" => ext.clone(),
+            "path" => path.clone(),
+            _ => folder.to_string(),
+        });
+
+    LOCAL_PATTERN
+        .replace_all(&escape_local_ident(&result), local_name)
+        .into_owned()
+}
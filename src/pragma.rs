@@ -0,0 +1,57 @@
+use swc_core::common::{comments::Comment, errors::HANDLER};
+
+use super::Prefer;
+
+/// 关闭整个文件的转换：`/* @jsx-css-modules-disable */`
+const DISABLE: &str = "@jsx-css-modules-disable";
+/// 文件级配置覆盖：`/* @jsx-css-modules prefer=global */`
+const OVERRIDE: &str = "@jsx-css-modules";
+/// 跳过紧随其后的一个 JSX 元素：`// jsx-css-modules-ignore-next-line`
+const IGNORE_NEXT_LINE: &str = "jsx-css-modules-ignore-next-line";
+
+/// 从文件开头的注释中读取的指令
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FilePragmas {
+    pub disabled: bool,
+    pub prefer: Option<Prefer>,
+}
+
+pub fn parse_file_pragmas(comments: &[Comment]) -> FilePragmas {
+    let mut pragmas = FilePragmas::default();
+    for comment in comments {
+        for line in comment.text.lines() {
+            let line = line.trim().trim_start_matches('*').trim();
+            if line.split_whitespace().any(|word| word == DISABLE) {
+                pragmas.disabled = true;
+                continue;
+            }
+            let Some(options) = line.strip_prefix(OVERRIDE) else {
+                continue;
+            };
+            if !options.starts_with(char::is_whitespace) {
+                continue;
+            }
+
+            for option in options.split_whitespace() {
+                let result = match option.split_once('=') {
+                    Some(("prefer", value)) => Prefer::try_from(value.to_string()).map(|prefer| {
+                        pragmas.prefer = Some(prefer);
+                    }),
+                    _ => Err(format!("unknown option `{}`", option)),
+                };
+                if let Err(err) = result {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_warn(comment.span, &format!("ignored {} pragma: {}", OVERRIDE, err))
+                            .emit()
+                    });
+                }
+            }
+        }
+    }
+    pragmas
+}
+
+pub fn has_ignore_next_line(comments: &[Comment]) -> bool {
+    comments.iter().any(|comment| comment.text.trim() == IGNORE_NEXT_LINE)
+}
@@ -0,0 +1,68 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use regex::Regex;
+
+static BLOCK_COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)/\*.*?\*/").unwrap());
+static LINE_COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)(^|[\s;{}])//[^\n]*").unwrap());
+static CLASS_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\.(-?[_a-zA-Z][_a-zA-Z0-9-]*)").unwrap());
+static IGNORED_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s):global\(.*?\)|\[.*?\]").unwrap());
+static GLOBAL_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s):global\b.*").unwrap());
+
+/// 从样式文件中提取出的局部类名
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Stylesheet {
+    pub path: PathBuf,
+    pub class_names: Vec<String>,
+}
+
+impl Stylesheet {
+    pub fn read(path: &Path) -> Option<Self> {
+        let source = fs::read_to_string(path).ok()?;
+        Some(Self {
+            path: path.to_path_buf(),
+            class_names: Self::parse(&source),
+        })
+    }
+
+    pub fn contains(&self, class_name: &str) -> bool {
+        self.class_names.iter().any(|name| name == class_name)
+    }
+
+    /// 只做轻量的选择器扫描：跳过注释、声明块内容和 `:global` 中的类名，
+    /// 不处理预处理器的嵌套拼接（如 `&-suffix`）
+    fn parse(source: &str) -> Vec<String> {
+        let source = BLOCK_COMMENT.replace_all(source, "");
+        let source = LINE_COMMENT.replace_all(&source, "$1");
+
+        let mut class_names: Vec<String> = vec![];
+        let mut prelude = String::new();
+        for c in source.chars() {
+            match c {
+                '{' => {
+                    let selector = prelude.trim();
+                    if !selector.starts_with('@') {
+                        let selector = IGNORED_PATTERN.replace_all(selector, "");
+                        for part in selector.split(',') {
+                            let part = GLOBAL_PATTERN.replace(part, "");
+                            for caps in CLASS_PATTERN.captures_iter(&part) {
+                                let class_name = caps[1].to_string();
+                                if !class_names.contains(&class_name) {
+                                    class_names.push(class_name);
+                                }
+                            }
+                        }
+                    }
+                    prelude.clear();
+                }
+                '}' | ';' => prelude.clear(),
+                _ => prelude.push(c),
+            }
+        }
+
+        class_names
+    }
+}
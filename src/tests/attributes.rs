@@ -0,0 +1,63 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{get_config, syntax, visit_mut_pass};
+use crate::{Config, visitor::JsxCssModulesVisitor};
+
+// test_custom_attributes
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            attributes: vec![
+                "className".to_string(),
+                "*ClassName".to_string(),
+                "class".to_string(),
+                "/^data-(cls|klass)$/".to_string(),
+            ],
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_custom_attributes,
+    r#"
+        import './styles.css';
+
+        const Component = () => (
+            <Card className="card" wrapperClassName="wrapper" iconClassName={icon}>
+                <NavLink to="/" activeClassName="active" title="title">Home</NavLink>
+                <div class="box" data-cls="cls" data-klass-name="raw">Hello</div>
+            </Card>
+        );
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () =>
+            <Card className={_matcher("card")} wrapperClassName={_matcher("wrapper")} iconClassName={_matcher(icon)}>
+                <NavLink to="/" activeClassName={_matcher("active")} title="title">Home</NavLink>
+                <div class={_matcher("box")} data-cls={_matcher("cls")} data-klass-name="raw">Hello</div>
+            </Card>;
+    "#
+);
+
+// test_default_attributes_only_class_name
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_default_attributes_only_class_name,
+    r#"
+        import './styles.css';
+
+        const Component = () => <div className="box" class="box" wrapperClassName="box">Hello</div>;
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () => <div className={_matcher("box")} class="box" wrapperClassName="box">Hello</div>;
+    "#
+);
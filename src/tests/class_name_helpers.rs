@@ -0,0 +1,132 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{fixture_visitor, get_config, syntax, visit_mut_pass};
+use crate::{Config, visitor::JsxCssModulesVisitor};
+
+// test_classnames_arguments
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_classnames_arguments,
+    r#"
+        import cx from 'classnames';
+        import './styles.css';
+
+        const Button = ({ size, isActive, open }) => (
+            <button className={cx('btn :global(ui)', { active: isActive, 'is-open': open }, size && 'btn-' + size, ['a', open && 'b'])}>
+                Click
+            </button>
+        );
+    "#,
+    r#"
+        import cx from 'classnames';
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Button = ({ size, isActive, open }) =>
+            <button className={cx(`${_styles.btn || "btn"} ui`, { [_styles.active || "active"]: isActive, [_styles["is-open"] || "is-open"]: open }, size && _matcher('btn-' + size), [_styles.a || "a", open && (_styles.b || "b")])}>
+                Click
+            </button>;
+    "#
+);
+
+// test_classnames_keeps_variable_arguments
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_classnames_keeps_variable_arguments,
+    r#"
+        import cx from 'classnames';
+        import './styles.css';
+
+        const classes = { active: true };
+        const extra = ['b'];
+        const Component = ({ props }) => (
+            <div className={cx('a', classes, [extra, props.className], getClasses(), `c-${props.size}`)} />
+        );
+    "#,
+    r#"
+        import cx from 'classnames';
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const classes = { active: true };
+        const extra = ['b'];
+        const Component = ({ props }) =>
+            <div className={cx(_styles.a || "a", classes, [extra, props.className], getClasses(), _matcher(`c-${props.size}`))} />;
+    "#
+);
+
+// test_clsx_named_import_and_shorthand
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_clsx_named_import_and_shorthand,
+    r#"
+        import { clsx } from 'clsx';
+        import './styles.css';
+
+        const Component = ({ active, name }) => (
+            <div className={open ? clsx('a', { active, [name]: true }) : 'b'}>
+                <span className={classnames('text')} />
+            </div>
+        );
+    "#,
+    r#"
+        import { clsx } from 'clsx';
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = ({ active, name }) =>
+            <div className={open ? clsx(_styles.a || "a", { [_styles.active || "active"]: active, [_matcher(name)]: true }) : _matcher("b")}>
+                <span className={_matcher(classnames('text'))} />
+            </div>;
+    "#
+);
+
+// test_classnames_require
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_classnames_require,
+    r#"
+        const classNames = require('classnames/dedupe');
+        require('./styles.css');
+
+        const Component = () => <div className={classNames('a', 'b')} />;
+    "#,
+    r#"
+        const classNames = require('classnames/dedupe');
+        const style_0 = require('./styles.css');
+        const _styles = Object.assign({}, style_0);
+
+        const Component = () => <div className={classNames(_styles.a || "a", _styles.b || "b")} />;
+    "#
+);
+
+// test_classnames_inline_local_idents
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(fixture_visitor(Config {
+        local_ident_name: Some("[name]__[local]___[hash:base64:5]".to_string()),
+        ..get_config()
+    })),
+    test_classnames_inline_local_idents,
+    r#"
+        import cx from 'classnames';
+        import './App.module.css';
+
+        const Component = ({ selected }) => <div className={cx('container', { title: selected })} />;
+    "#,
+    r#"
+        import cx from 'classnames';
+        import './App.module.css';
+
+        const Component = ({ selected }) => <div className={cx("App-module__container___CHpsS", { "App-module__title___LR8yx": selected })} />;
+    "#
+);
@@ -0,0 +1,80 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{get_config, syntax, visit_mut_pass};
+use crate::visitor::JsxCssModulesVisitor;
+
+// test_script_bare_require
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_script_bare_require,
+    r#"
+        const React = require('react');
+        require('./styles.css');
+
+        const Component = () => <div className="container">Hello</div>;
+        module.exports = Component;
+    "#,
+    r#"
+        const React = require('react');
+        const style_0 = require('./styles.css');
+        const getMatcher = require('swc-plugin-jsx-css-modules/helpers').getMatcher;
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () => <div className={_matcher("container")}>Hello</div>;
+        module.exports = Component;
+    "#
+);
+
+// test_script_require_bindings
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_script_require_bindings,
+    r#"
+        const styles = require('./a.module.css');
+        const { title } = require('./b.module.css');
+        require('./c.less');
+
+        const Component = () => <div className={styles.root}>
+            <span className="text">{title}</span>
+        </div>;
+    "#,
+    r#"
+        const styles = require('./a.module.css');
+        const { title } = require('./b.module.css');
+        const style_1 = require('./b.module.css');
+        const style_2 = require('./c.less');
+        const getMatcher = require('swc-plugin-jsx-css-modules/helpers').getMatcher;
+        const _styles = Object.assign({}, styles, style_1, style_2);
+        const _matcher = getMatcher(_styles, 'local', { styles });
+
+        const Component = () => <div className={_matcher(styles.root)}>
+            <span className={_matcher("text")}>{title}</span>
+        </div>;
+    "#
+);
+
+// test_module_with_style_require
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_module_with_style_require,
+    r#"
+        import './base.css';
+        import React from 'react';
+        require('./theme.css');
+
+        export const Component = () => <div className="container">Hello</div>;
+    "#,
+    r#"
+        import style_0 from './base.css';
+        import React from 'react';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const style_1 = require('./theme.css');
+        const _styles = Object.assign({}, style_0, style_1);
+        const _matcher = getMatcher(_styles, 'local');
+
+        export const Component = () => <div className={_matcher("container")}>Hello</div>;
+    "#
+);
@@ -0,0 +1,168 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{syntax, visit_mut_pass};
+use crate::{Config, Elements, Framework, Helper, ImportStyle, MissingClass, Prefer, UsageManifest, visitor::JsxCssModulesVisitor};
+
+#[test]
+fn test_parse_config() {
+    let config = Config::parse(
+        r#"{ "prefer": "global", "importStyle": "namespace", "missingClass": "warn", "styleFileReg": ["\\.css$"] }"#,
+    )
+    .unwrap();
+    assert_eq!(config.prefer, Prefer::Global);
+    assert_eq!(config.import_style, ImportStyle::Namespace);
+    assert_eq!(config.missing_class, MissingClass::Warn);
+    assert_eq!(config.style_file_reg, vec![r"\.css$"]);
+
+    let config = Config::parse(r#"{ "prefer": "strict-local", "importStyle": "named" }"#).unwrap();
+    assert_eq!(config.prefer, Prefer::StrictLocal);
+    assert_eq!(config.import_style, ImportStyle::Named);
+
+    let config = Config::parse(r#"{ "usageManifest": "comment" }"#).unwrap();
+    assert_eq!(config.usage_manifest, UsageManifest::Comment);
+
+    let config = Config::parse("").unwrap();
+    assert_eq!(config.prefer, Prefer::Local);
+    assert_eq!(config.attributes, vec!["className"]);
+}
+
+#[test]
+fn test_parse_helper() {
+    assert_eq!(Config::parse("{}").unwrap().helper, Helper::Import);
+    assert_eq!(Config::parse(r#"{ "helper": "inline" }"#).unwrap().helper, Helper::Inline);
+    assert_eq!(
+        Config::parse(r#"{ "helper": { "module": "@acme/css-runtime", "export": "createMatcher" } }"#)
+            .unwrap()
+            .helper,
+        Helper::Custom {
+            module: "@acme/css-runtime".to_string(),
+            export: "createMatcher".to_string(),
+        }
+    );
+
+    let err = Config::parse(r#"{ "helper": "inlined" }"#).unwrap_err();
+    assert!(err.contains(r#"invalid `helper` value "inlined""#), "{}", err);
+
+    let err = Config::parse(r#"{ "helper": { "module": "x" } }"#).unwrap_err();
+    assert!(err.contains(r#"invalid `helper` value {"module":"x"}: missing `export`"#), "{}", err);
+
+    let err = Config::parse(r#"{ "helper": { "module": "x", "exprt": "y" } }"#).unwrap_err();
+    assert!(err.contains("unknown field `exprt`, expected `module` or `export`"), "{}", err);
+
+    let err = Config::parse(r#"{ "helper": { "module": "x", "export": 1 } }"#).unwrap_err();
+    assert!(err.contains("`export` must be a string"), "{}", err);
+}
+
+#[test]
+fn test_parse_elements() {
+    assert_eq!(Config::parse("{}").unwrap().elements, Elements::All);
+    assert_eq!(Config::parse(r#"{ "elements": "intrinsic" }"#).unwrap().elements, Elements::Intrinsic);
+    assert_eq!(
+        Config::parse(r#"{ "elements": { "exclude": ["Button"] } }"#).unwrap().elements,
+        Elements::Filter {
+            include: vec![],
+            exclude: vec!["Button".to_string()],
+        }
+    );
+
+    let err = Config::parse(r#"{ "elements": "host" }"#).unwrap_err();
+    assert!(err.contains(r#"invalid `elements` value "host""#), "{}", err);
+
+    let err = Config::parse(r#"{ "elements": { "inclde": ["div"] } }"#).unwrap_err();
+    assert!(err.contains("unknown field `inclde`, expected `include` or `exclude`"), "{}", err);
+
+    let err = Config::parse(r#"{ "elements": { "include": "div" } }"#).unwrap_err();
+    assert!(err.contains(r#"invalid `elements` value {"include":"div"}: `include` must be an array of strings"#), "{}", err);
+}
+
+#[test]
+fn test_parse_framework() {
+    assert_eq!(Config::parse("{}").unwrap().framework, Framework::React);
+    assert_eq!(Config::parse(r#"{ "framework": "solid" }"#).unwrap().framework, Framework::Solid);
+    assert_eq!(Config::parse(r#"{ "framework": "qwik" }"#).unwrap().framework, Framework::Qwik);
+
+    let err = Config::parse(r#"{ "framework": "svelte" }"#).unwrap_err();
+    assert!(err.contains(r#"invalid `framework` value "svelte""#), "{}", err);
+}
+
+#[test]
+fn test_parse_style_name() {
+    assert!(!Config::parse("{}").unwrap().style_name);
+    assert!(Config::parse(r#"{ "styleName": true }"#).unwrap().style_name);
+}
+
+#[test]
+fn test_reject_unknown_field() {
+    let err = Config::parse(r#"{ "stylefileReg": [] }"#).unwrap_err();
+    assert!(err.contains("unknown field `stylefileReg`"), "{}", err);
+}
+
+#[test]
+fn test_reject_invalid_enum_value() {
+    let err = Config::parse(r#"{ "prefer": "globl" }"#).unwrap_err();
+    assert!(err.contains(r#"invalid `prefer` value "globl""#), "{}", err);
+
+    let err = Config::parse(r#"{ "importStyle": "defualt" }"#).unwrap_err();
+    assert!(err.contains(r#"invalid `importStyle` value "defualt""#), "{}", err);
+}
+
+#[test]
+fn test_reject_invalid_regex() {
+    let config = Config {
+        style_file_reg: vec![r"\.(css$".to_string()],
+        ..Default::default()
+    };
+    let err = JsxCssModulesVisitor::try_new(config).err().unwrap();
+    assert!(err.starts_with(r#"invalid `styleFileReg` pattern "\.(css$""#), "{}", err);
+
+    let config = Config {
+        attributes: vec!["/[/".to_string()],
+        ..Default::default()
+    };
+    let err = JsxCssModulesVisitor::try_new(config).err().unwrap();
+    assert!(err.starts_with(r#"invalid `attributes` pattern "/[/""#), "{}", err);
+
+    let config = Config {
+        style_file_glob: vec!["**/*.module.[css".to_string()],
+        ..Default::default()
+    };
+    let err = JsxCssModulesVisitor::try_new(config).err().unwrap();
+    assert!(err.starts_with(r#"invalid `styleFileGlob` pattern "**/*.module.[css""#), "{}", err);
+
+    let config = Config {
+        elements: Elements::Filter {
+            include: vec!["/(/".to_string()],
+            exclude: vec![],
+        },
+        ..Default::default()
+    };
+    let err = JsxCssModulesVisitor::try_new(config).err().unwrap();
+    assert!(err.starts_with(r#"invalid `elements` pattern "/(/""#), "{}", err);
+}
+
+// test_style_file_glob
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            style_file_glob: vec!["**/*.module.css".to_string(), "**/*.module.scss".to_string()],
+            ..Default::default()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_style_file_glob,
+    r#"
+        import './global.css';
+        import '../components/Button.module.css';
+
+        const Component = () => <div className="a">Hello</div>;
+    "#,
+    r#"
+        import './global.css';
+        import style_0 from '../components/Button.module.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () => <div className={_matcher("a")}>Hello</div>;
+    "#
+);
@@ -0,0 +1,103 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{get_config, syntax, visit_mut_pass};
+use crate::{Config, visitor::JsxCssModulesVisitor};
+
+// test_non_string_expressions_untouched
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_non_string_expressions_untouched,
+    r#"
+        import './styles.css';
+
+        const Component = () => (
+            <div className={undefined}>
+                <span className={null} />
+                <span className={1} />
+                <span className={void 0} />
+                <List className={['a', 'b']} />
+                <List className={{ a: true }} />
+                <List className={<b />} />
+            </div>
+        );
+    "#,
+    r#"
+        import './styles.css';
+
+        const Component = () =>
+            <div className={undefined}>
+                <span className={null} />
+                <span className={1} />
+                <span className={void 0} />
+                <List className={['a', 'b']} />
+                <List className={{ a: true }} />
+                <List className={<b />} />
+            </div>;
+    "#
+);
+
+// test_matcher_pushed_into_branches
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_matcher_pushed_into_branches,
+    r#"
+        import './styles.css';
+
+        const Component = () => (
+            <div className={active ? "a" : "b"}>
+                <span className={visible && "shown"} />
+                <span className={name || "fallback"} />
+                <span className={name ?? undefined} />
+                <span className={(first ? "x" : null)} />
+            </div>
+        );
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () =>
+            <div className={active ? _matcher("a") : _matcher("b")}>
+                <span className={visible && _matcher("shown")} />
+                <span className={_matcher(name) || _matcher("fallback")} />
+                <span className={_matcher(name) ?? undefined} />
+                <span className={first ? _matcher("x") : null} />
+            </div>;
+    "#
+);
+
+// test_static_resolve_literal_branches
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            static_resolve: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_static_resolve_literal_branches,
+    r#"
+        import './styles.css';
+
+        const Component = () => (
+            <div className={active ? "a :global(b)" : `c`}>
+                <span className={visible && getClassName()} />
+            </div>
+        );
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () =>
+            <div className={active ? `${_styles.a || "a"} b` : _styles.c || "c"}>
+                <span className={visible && _matcher(getClassName())} />
+            </div>;
+    "#
+);
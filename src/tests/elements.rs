@@ -0,0 +1,80 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{get_config, syntax, visit_mut_pass};
+use crate::{visitor::JsxCssModulesVisitor, Config, Elements};
+
+// test_intrinsic_elements_only
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            elements: Elements::Intrinsic,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_intrinsic_elements_only,
+    r#"
+        import './a.css';
+
+        const Component = () => (
+            <div className="a">
+                <Button className="btn-primary" />
+                <ui.Card className="card" />
+                <svg:rect className="shape" />
+            </div>
+        );
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () => <div className={_matcher("a")}>
+                <Button className="btn-primary" />
+                <ui.Card className="card" />
+                <svg:rect className={_matcher("shape")} />
+            </div>;
+    "#
+);
+
+// test_include_and_exclude_elements
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            elements: Elements::Filter {
+                include: vec!["div".to_string(), "ui.*".to_string(), "Button".to_string()],
+                exclude: vec!["ui.Raw".to_string()],
+            },
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_include_and_exclude_elements,
+    r#"
+        import './a.css';
+
+        const Component = () => (
+            <div className="a">
+                <span className="b" />
+                <Button className="c" />
+                <ui.Card className="d" />
+                <ui.Raw className="e" />
+            </div>
+        );
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () => <div className={_matcher("a")}>
+                <span className="b" />
+                <Button className={_matcher("c")} />
+                <ui.Card className={_matcher("d")} />
+                <ui.Raw className="e" />
+            </div>;
+    "#
+);
@@ -0,0 +1,212 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{get_config, syntax, visit_mut_pass};
+use crate::{visitor::JsxCssModulesVisitor, Config, Framework};
+
+// test_solid_class_list
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            framework: Framework::Solid,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_solid_class_list,
+    r#"
+        import './a.css';
+
+        const Component = (props) => (
+            <div
+                class="a :global(b)"
+                classList={{ active: isActive(), "btn-primary": true, ":global(shown)": props.shown, [props.extra]: true }}
+            />
+        );
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = (props) => <div
+            class={_matcher("a :global(b)")}
+            classList={{ [_styles.active || "active"]: isActive(), [_styles["btn-primary"] || "btn-primary"]: true, "shown": props.shown, [_matcher(props.extra)]: true }}
+        />;
+    "#
+);
+
+// test_vue_object_and_array_class
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            framework: Framework::Vue,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_vue_object_and_array_class,
+    r#"
+        import './a.css';
+
+        const Component = () => (
+            <div class={{ active: isActive.value }}>
+                <span class={["item", { selected: isSelected }, extra]} />
+            </div>
+        );
+    "#,
+    r#"
+        import style_0 from './a.css';
+        const _styles = Object.assign({}, style_0);
+
+        const Component = () => <div class={{ [_styles.active || "active"]: isActive.value }}>
+                <span class={[_styles.item || "item", { [_styles.selected || "selected"]: isSelected }, extra]} />
+            </div>;
+    "#
+);
+
+// test_react_keeps_object_class
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            framework: Framework::React,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_react_keeps_object_class,
+    r#"
+        import './a.css';
+
+        const Component = () => <div className={{ active: true }} class="raw" />;
+    "#,
+    r#"
+        import './a.css';
+
+        const Component = () => <div className={{ active: true }} class="raw" />;
+    "#
+);
+
+// test_preact_class_and_class_name
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            framework: Framework::Preact,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_preact_class_and_class_name,
+    r#"
+        import './a.css';
+
+        const Component = () => <div class="a"><Button className="b" class={{ c: true }} /></div>;
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () => <div class={_matcher("a")}><Button className={_matcher("b")} class={{ c: true }} /></div>;
+    "#
+);
+
+// test_solid_class_list_on_component
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            framework: Framework::Solid,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_solid_class_list_on_component,
+    r#"
+        import './a.css';
+
+        const Component = () => (
+            <ui.Card class="card" classList={{ active: true }}>
+                <Button class="button" classList={{ active: true }} />
+            </ui.Card>
+        );
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () => <ui.Card class={_matcher("card")} classList={{ active: true }}>
+                <Button class={_matcher("button")} classList={{ active: true }} />
+            </ui.Card>;
+    "#
+);
+
+// test_qwik_class_list
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            framework: Framework::Qwik,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_qwik_class_list,
+    r#"
+        import './a.css';
+
+        const Component = component$(() => (
+            <div class:list={["a", { b: on.value }, extra]}>
+                <Link class:list="link" />
+            </div>
+        ));
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = component$(() => <div class:list={[_styles.a || "a", { [_styles.b || "b"]: on.value }, extra]}>
+                <Link class:list={_matcher("link")} />
+            </div>);
+    "#
+);
+
+// test_qwik_class_binding
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            framework: Framework::Qwik,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_qwik_class_binding,
+    r#"
+        import './a.css';
+
+        const Component = component$(() => (
+            <div class={["a :global(b)", { c: isC.value }]}>
+                <Link class="link" />
+            </div>
+        ));
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = component$(() => <div class={[`${_styles.a || "a"} b`, { [_styles.c || "c"]: isC.value }]}>
+                <Link class={_matcher("link")} />
+            </div>);
+    "#
+);
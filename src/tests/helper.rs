@@ -0,0 +1,129 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{get_config, syntax, visit_mut_pass};
+use crate::{Config, Helper, visitor::JsxCssModulesVisitor};
+
+fn get_custom_helper() -> Helper {
+    Helper::Custom {
+        module: "@acme/css-runtime".to_string(),
+        export: "createMatcher".to_string(),
+    }
+}
+
+// test_inline_helper
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            helper: Helper::Inline,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_inline_helper,
+    r#"
+        import './a.css';
+        import './b.css';
+
+        const Component = () => <div className="a"><span className="b" /></div>;
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import style_1 from './b.css';
+        function getMatcher(styles, prefer, scopes) {
+          var splitString = function (string) {
+            return string.trim().split(' ').filter(Boolean);
+          };
+          var scopePatterns = Object.keys(scopes || {}).map(function (name) {
+            return { name: name, pattern: new RegExp(':' + name.replace(/\$/g, '\\$') + '\\([\\s\\S]*?\\)', 'g') };
+          });
+          return function (classNames) {
+            if (typeof classNames !== 'string') {
+              return null;
+            }
+            var globalClassNames = [];
+            var localClassNames = [];
+            var scopedClassNames = [];
+            var restString = classNames.replace(/\s{2,}/g, ' ').replace(/:global\([\s\S]*?\)/g, function (text) {
+              globalClassNames = globalClassNames.concat(splitString(text.replace(/(:global\(|\))/g, '')));
+              return '';
+            }).replace(/:local\([\s\S]*?\)/g, function (text) {
+              localClassNames = localClassNames.concat(splitString(text.replace(/(:local\(|\))/g, '')));
+              return '';
+            });
+            scopePatterns.forEach(function (scope) {
+              restString = restString.replace(scope.pattern, function (text) {
+                splitString(text.slice(scope.name.length + 2, -1)).forEach(function (className) {
+                  scopedClassNames.push(scopes[scope.name][className] || className);
+                });
+                return '';
+              });
+            });
+            var restClassNames = splitString(restString);
+            if (prefer === 'global') {
+              globalClassNames = globalClassNames.concat(restClassNames);
+            } else {
+              localClassNames = localClassNames.concat(restClassNames);
+            }
+            return localClassNames.map(function (className) {
+              return styles[className] || className;
+            }).concat(scopedClassNames).concat(globalClassNames).join(' ').trim();
+          };
+        }
+        const _styles = Object.assign({}, style_0, style_1);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () => <div className={_matcher("a")}><span className={_matcher("b")} /></div>;
+    "#
+);
+
+// test_custom_helper_import
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            helper: get_custom_helper(),
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_custom_helper_import,
+    r#"
+        import './a.css';
+
+        const Component = () => <div className="a" />;
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { createMatcher as getMatcher } from '@acme/css-runtime';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () => <div className={_matcher("a")} />;
+    "#
+);
+
+// test_custom_helper_require
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            helper: get_custom_helper(),
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_custom_helper_require,
+    r#"
+        require('./a.css');
+
+        const Component = () => <div className="a" />;
+    "#,
+    r#"
+        const style_0 = require('./a.css');
+        const getMatcher = require('@acme/css-runtime').createMatcher;
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () => <div className={_matcher("a")} />;
+    "#
+);
@@ -0,0 +1,112 @@
+use std::path::Path;
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{fixture_visitor, get_config, syntax, visit_mut_pass, FIXTURES};
+use crate::{
+    local_ident::generate_local_ident, stylesheet::Stylesheet, Config,
+    visitor::JsxCssModulesVisitor,
+};
+
+fn get_local_ident_visitor() -> JsxCssModulesVisitor {
+    fixture_visitor(Config {
+        local_ident_name: Some("[name]__[local]___[hash:base64:5]".to_string()),
+        ..get_config()
+    })
+}
+
+#[test]
+fn test_stylesheet_class_names() {
+    let stylesheet = Stylesheet::read(&Path::new(FIXTURES).join("App.module.css")).unwrap();
+    assert_eq!(stylesheet.class_names, vec!["container", "title", "subtitle", "content", "item"]);
+
+    let stylesheet = Stylesheet::read(&Path::new(FIXTURES).join("Button.module.scss")).unwrap();
+    assert_eq!(stylesheet.class_names, vec!["button", "icon"]);
+}
+
+#[test]
+fn test_generate_local_ident() {
+    let context = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let resource_path = Path::new(FIXTURES).join("App.module.css");
+    assert_eq!(
+        generate_local_ident("[name]__[local]___[hash:base64:5]", context, &resource_path, "item", None),
+        "App-module__item____8WQx"
+    );
+    assert_eq!(
+        generate_local_ident("[hash:hex:8]", context, &resource_path, "container", None),
+        "a6c485bd"
+    );
+}
+
+// test_local_ident_inline_literals
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(get_local_ident_visitor()),
+    test_local_ident_inline_literals,
+    r#"
+        import './App.module.css';
+        import './Button.module.scss';
+
+        const Component = () => (
+            <div className="container :global(app) missing">
+                <button className="button icon">Click me</button>
+            </div>
+        );
+    "#,
+    r#"
+        import './App.module.css';
+        import './Button.module.scss';
+
+        const Component = () =>
+            <div className="App-module__container___CHpsS missing app">
+                <button className="Button-module__button___ic4pm Button-module__icon___LVYoH">Click me</button>
+            </div>;
+    "#
+);
+
+// test_local_ident_with_dynamic_class_names
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(get_local_ident_visitor()),
+    test_local_ident_with_dynamic_class_names,
+    r#"
+        import './App.module.css';
+
+        const Component = () => (
+            <div className="title">
+                <span className={`item ${active}`}>Hello</span>
+            </div>
+        );
+    "#,
+    r#"
+        import style_0 from './App.module.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () =>
+            <div className="App-module__title___LR8yx">
+                <span className={_matcher(`item ${active}`)}>Hello</span>
+            </div>;
+    "#
+);
+
+// test_local_ident_missing_stylesheet
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(get_local_ident_visitor()),
+    test_local_ident_missing_stylesheet,
+    r#"
+        import './App.module.css';
+        import './Missing.module.css';
+
+        const Component = () => <div className="container">Hello</div>;
+    "#,
+    r#"
+        import style_0 from './App.module.css';
+        import style_1 from './Missing.module.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0, style_1);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () => <div className={_matcher("container")}>Hello</div>;
+    "#
+);
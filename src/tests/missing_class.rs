@@ -0,0 +1,70 @@
+use swc_core::common::errors::Level;
+use super::{collect_diagnostics, fixture_visitor, get_config};
+use crate::{Config, MissingClass, Prefer, visitor::JsxCssModulesVisitor};
+
+fn get_visitor(missing_class: MissingClass) -> JsxCssModulesVisitor {
+    fixture_visitor(Config {
+        missing_class,
+        ..get_config()
+    })
+}
+
+const SOURCE: &str = r#"
+import './App.module.css';
+
+const Component = () => (
+    <div className="contianer :global(app)">
+        <span className="title">Hello</span>
+        <span className=":local(unknown)">World</span>
+    </div>
+);
+"#;
+
+#[test]
+fn test_missing_class_warning() {
+    let diagnostics = collect_diagnostics(get_visitor(MissingClass::Warn), SOURCE);
+    assert_eq!(diagnostics.len(), 2);
+
+    let (diagnostic, line) = &diagnostics[0];
+    assert_eq!(diagnostic.level, Level::Warning);
+    assert_eq!(*line, 5);
+    assert_eq!(diagnostic.message(), "class `contianer` does not exist in the imported stylesheets");
+    assert_eq!(diagnostic.children[0].message(), "did you mean `container`?");
+
+    let (diagnostic, line) = &diagnostics[1];
+    assert_eq!(*line, 7);
+    assert_eq!(diagnostic.message(), "class `unknown` does not exist in the imported stylesheets");
+    assert!(diagnostic.children.is_empty());
+}
+
+#[test]
+fn test_missing_class_error() {
+    let diagnostics = collect_diagnostics(get_visitor(MissingClass::Error), SOURCE);
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|(diagnostic, _)| diagnostic.level == Level::Error));
+}
+
+#[test]
+fn test_missing_class_ignore() {
+    assert!(collect_diagnostics(get_visitor(MissingClass::Ignore), SOURCE).is_empty());
+}
+
+#[test]
+fn test_strict_local_reports_missing_class() {
+    let visitor = fixture_visitor(Config {
+        prefer: Prefer::StrictLocal,
+        ..get_config()
+    });
+    let diagnostics = collect_diagnostics(visitor, SOURCE);
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|(diagnostic, _)| diagnostic.level == Level::Error));
+}
+
+#[test]
+fn test_missing_class_without_stylesheet() {
+    let source = r#"
+        import './Missing.module.css';
+        const Component = () => <div className="contianer">Hello</div>;
+    "#;
+    assert!(collect_diagnostics(get_visitor(MissingClass::Warn), source).is_empty());
+}
@@ -1,26 +1,69 @@
-use swc_core::ecma::{
-    ast::{self, *},
-    parser::{EsSyntax, Syntax},
-    transforms::testing::test_inline,
-    visit::Fold,
+use std::sync::{Arc, Mutex};
+use swc_core::{
+    common::{
+        errors::{Diagnostic, DiagnosticBuilder, Emitter, Handler, HANDLER},
+        sync::Lrc,
+        FileName, SourceMap, GLOBALS,
+    },
+    ecma::{
+        ast::*,
+        parser::{parse_file_as_module, EsSyntax, Syntax},
+        transforms::testing::test_inline,
+        visit::{visit_mut_pass, VisitMutWith},
+    },
 };
-use crate::{Config, visitor::JsxCssModulesVisitor};
+use crate::{Config, ImportStyle, Prefer, visitor::JsxCssModulesVisitor};
 
-struct AsFolder<T>(T);
+mod attributes;
+mod class_name_helpers;
+mod commonjs;
+mod config;
+mod dynamic_class_name;
+mod elements;
+mod framework;
+mod helper;
+mod local_ident;
+mod missing_class;
+mod module_order;
+mod named_import;
+mod nested_jsx;
+mod pragma;
+mod scopes;
+mod source_map;
+mod static_resolve;
+mod style_name;
+mod usage_manifest;
 
-impl<T: Fold> ast::Pass for AsFolder<T> {
-    fn process(&mut self, program: &mut Program) {
-        match program {
-            Program::Module(module) => {
-                *module = self.0.fold_module(module.clone());
-            }
-            _ => {}
-        }
+#[derive(Clone, Default)]
+struct CollectingEmitter(Arc<Mutex<Vec<Diagnostic>>>);
+
+impl Emitter for CollectingEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        self.0.lock().unwrap().push((**db).clone());
     }
 }
 
-fn as_folder<T: Fold>(t: T) -> impl ast::Pass {
-    AsFolder(t)
+// 执行转换并收集产生的诊断信息，按源码位置排序后返回 (诊断, 诊断所在的行号)
+fn collect_diagnostics(mut visitor: JsxCssModulesVisitor, src: &str) -> Vec<(Diagnostic, usize)> {
+    GLOBALS.set(&Default::default(), || {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(Lrc::new(FileName::Anon), src.to_string());
+        let mut module = parse_file_as_module(&fm, syntax(), EsVersion::latest(), None, &mut vec![]).unwrap();
+
+        let emitter = CollectingEmitter::default();
+        let handler = Handler::with_emitter(true, false, Box::new(emitter.clone()));
+        HANDLER.set(&handler, || module.visit_mut_with(&mut visitor));
+
+        let mut diagnostics = emitter.0.lock().unwrap().clone();
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span.primary_span().unwrap().lo);
+        diagnostics
+            .into_iter()
+            .map(|diagnostic| {
+                let line = cm.lookup_char_pos(diagnostic.span.primary_span().unwrap().lo).line;
+                (diagnostic, line)
+            })
+            .collect()
+    })
 }
 
 fn syntax() -> Syntax {
@@ -30,11 +73,22 @@ fn syntax() -> Syntax {
     })
 }
 
+// 样式文件夹具所在目录
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/fixtures");
+
+// 以 fixtures/App.jsx 作为当前文件，使相对路径导入的样式文件可以在构建期读取
+fn fixture_visitor(config: Config) -> JsxCssModulesVisitor {
+    JsxCssModulesVisitor::new(config)
+        .with_filename(format!("{}/App.jsx", FIXTURES))
+        .with_cwd(env!("CARGO_MANIFEST_DIR"))
+}
+
 fn get_config() -> Config {
     Config {
-        prefer: "local".to_string(),
+        prefer: Prefer::Local,
         style_file_reg: vec![r"\.(css|scss|sass|less)$".to_string()],
-        import_style: "default".to_string(),
+        import_style: ImportStyle::Default,
+        ..Default::default()
     }
 }
 
@@ -43,7 +97,7 @@ test_inline!(
     syntax(),
     |_| {
         let config = get_config();
-        as_folder(JsxCssModulesVisitor::new(config))
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
     },
     test_existing_default_import,
     r#"
@@ -71,7 +125,7 @@ test_inline!(
     syntax(),
     |_| {
         let config = get_config();
-        as_folder(JsxCssModulesVisitor::new(config))
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
     },
     test_multiple_style_imports,
     r#"
@@ -102,7 +156,7 @@ test_inline!(
     syntax(),
     |_| {
         let config = get_config();
-        as_folder(JsxCssModulesVisitor::new(config))
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
     },
     test_global_and_local_classes,
     r#"
@@ -130,11 +184,12 @@ test_inline!(
     syntax(),
     |_| {
         let config = Config {
-            prefer: "global".to_string(),
+            prefer: Prefer::Global,
             style_file_reg: vec![r"\.(css|scss|sass|less)$".to_string()],
-            import_style: "default".to_string(),
+            import_style: ImportStyle::Default,
+            ..get_config()
         };
-        as_folder(JsxCssModulesVisitor::new(config))
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
     },
     test_prefer_global,
     r#"
@@ -162,11 +217,12 @@ test_inline!(
     syntax(),
     |_| {
         let config = Config {
-            prefer: "local".to_string(),
+            prefer: Prefer::Local,
             style_file_reg: vec![r"\.module\.scss$".to_string()],
-            import_style: "default".to_string(),
+            import_style: ImportStyle::Default,
+            ..get_config()
         };
-        as_folder(JsxCssModulesVisitor::new(config))
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
     },
     test_only_module_scss,
     r#"
@@ -198,11 +254,12 @@ test_inline!(
     syntax(),
     |_| {
         let config = Config {
-            prefer: "global".to_string(),
+            prefer: Prefer::Global,
             style_file_reg: vec![r"\.module\.scss$".to_string()],
-            import_style: "default".to_string(),
+            import_style: ImportStyle::Default,
+            ..get_config()
         };
-        as_folder(JsxCssModulesVisitor::new(config))
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
     },
     test_comprehensive,
     r#"
@@ -238,11 +295,12 @@ test_inline!(
     syntax(),
     |_| {
         let config = Config {
-            prefer: "local".to_string(),
+            prefer: Prefer::Local,
             style_file_reg: vec![r"\.(css|scss|sass|less)$".to_string()],
-            import_style: "namespace".to_string(),
+            import_style: ImportStyle::Namespace,
+            ..get_config()
         };
-        as_folder(JsxCssModulesVisitor::new(config))
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
     },
     test_namespace_import_style,
     r#"
@@ -268,11 +326,12 @@ test_inline!(
     syntax(),
     |_| {
         let config = Config {
-            prefer: "local".to_string(),
+            prefer: Prefer::Local,
             style_file_reg: vec![r"\.(css|scss|sass|less)$".to_string()],
-            import_style: "default".to_string(),
+            import_style: ImportStyle::Default,
+            ..get_config()
         };
-        as_folder(JsxCssModulesVisitor::new(config))
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
     },
     test_default_import_style,
     r#"
@@ -298,11 +357,12 @@ test_inline!(
     syntax(),
     |_| {
         let config = Config {
-            prefer: "local".to_string(),
+            prefer: Prefer::Local,
             style_file_reg: vec![r"\.css$".to_string(), r"\.scss$".to_string()],
-            import_style: "default".to_string(),
+            import_style: ImportStyle::Default,
+            ..get_config()
         };
-        as_folder(JsxCssModulesVisitor::new(config))
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
     },
     test_template_string_classes,
     r#"
@@ -316,11 +376,11 @@ test_inline!(
         );
     "#,
     r#"
-        import style_0 from './style1.css';
-        import style_1 from './style2.scss';
+        import style1 from './style1.css';
+        import style2 from './style2.scss';
         import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
-        const _styles = Object.assign({}, style_0, style_1);
-        const _matcher = getMatcher(_styles, 'local');
+        const _styles = Object.assign({}, style1, style2);
+        const _matcher = getMatcher(_styles, 'local', { style1, style2 });
         const Component = () => 
             <div className={_matcher(`container ${someCondition ? 'active' : ''} wrapper`)}>
                 <span className={_matcher(`text bold`)}>Hello</span>
@@ -333,11 +393,12 @@ test_inline!(
     syntax(),
     |_| {
         let config = Config {
-            prefer: "local".to_string(),
+            prefer: Prefer::Local,
             style_file_reg: vec![r"\.css$".to_string(), r"\.scss$".to_string()],
-            import_style: "default".to_string(),
+            import_style: ImportStyle::Default,
+            ..get_config()
         };
-        as_folder(JsxCssModulesVisitor::new(config))
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
     },
     test_classnames_function,
     r#"
@@ -351,14 +412,199 @@ test_inline!(
         );
     "#,
     r#"
-        import style_0 from './style1.css';
-        import style_1 from './style2.scss';
+        import style1 from './style1.css';
+        import style2 from './style2.scss';
         import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
-        const _styles = Object.assign({}, style_0, style_1);
-        const _matcher = getMatcher(_styles, 'local');
+        const _styles = Object.assign({}, style1, style2);
+        const _matcher = getMatcher(_styles, 'local', { style1, style2 });
         const Component = () => 
             <div className={_matcher(classnames('container', 'wrapper', {'active': isActive}))}>
                 <span className={_matcher(classnames('text', 'bold'))}>Hello</span>
             </div>;
     "#
 );
+
+// test_preserve_user_bindings
+test_inline!(
+    syntax(),
+    |_| {
+        let config = get_config();
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_preserve_user_bindings,
+    r#"
+        import styles from './a.module.css';
+        import * as theme from './theme.css';
+        import './b.css';
+
+        const Component = () => (
+            <div className={styles.wrapper}>
+                <span className="text">Hello</span>
+            </div>
+        );
+    "#,
+    r#"
+        import styles from './a.module.css';
+        import * as theme from './theme.css';
+        import style_2 from './b.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, styles, theme, style_2);
+        const _matcher = getMatcher(_styles, 'local', { styles, theme });
+        const Component = () =>
+            <div className={_matcher(styles.wrapper)}>
+                <span className={_matcher("text")}>Hello</span>
+            </div>;
+    "#
+);
+
+// test_preserve_named_specifiers
+test_inline!(
+    syntax(),
+    |_| {
+        let config = get_config();
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_preserve_named_specifiers,
+    r#"
+        import { foo } from './a.css';
+        import styles, { bar } from './b.css';
+
+        const Component = () => <div className="container">{foo}{bar}</div>;
+    "#,
+    r#"
+        import style_0, { foo } from './a.css';
+        import styles, { bar } from './b.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0, styles);
+        const _matcher = getMatcher(_styles, 'local', { styles });
+        const Component = () => <div className={_matcher("container")}>{foo}{bar}</div>;
+    "#
+);
+
+// test_preserve_named_specifiers_with_namespace_style
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            import_style: ImportStyle::Namespace,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_preserve_named_specifiers_with_namespace_style,
+    r#"
+        import { foo } from './a.css';
+
+        const Component = () => <div className="container">{foo}</div>;
+    "#,
+    r#"
+        import { foo } from './a.css';
+        import * as style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+        const Component = () => <div className={_matcher("container")}>{foo}</div>;
+    "#
+);
+
+// test_generated_getmatcher_collision
+test_inline!(
+    syntax(),
+    |_| {
+        let config = get_config();
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_generated_getmatcher_collision,
+    r#"
+        import './styles.css';
+        import { getMatcher } from './utils';
+
+        const matcher = getMatcher();
+        const Component = () => <div className="container">Hello</div>;
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        import { getMatcher } from './utils';
+        import { getMatcher as getMatcher1 } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher1(_styles, 'local');
+        const matcher = getMatcher();
+        const Component = () => <div className={_matcher("container")}>Hello</div>;
+    "#
+);
+
+// test_generated_styles_collision
+test_inline!(
+    syntax(),
+    |_| {
+        let config = get_config();
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_generated_styles_collision,
+    r#"
+        import './styles.css';
+
+        const _styles = { container: 'user' };
+        const Component = () => <div className="container">{_styles.container}</div>;
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+        const _styles1 = { container: 'user' };
+        const Component = () => <div className={_matcher("container")}>{_styles1.container}</div>;
+    "#
+);
+
+// test_generated_matcher_collision
+test_inline!(
+    syntax(),
+    |_| {
+        let config = get_config();
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_generated_matcher_collision,
+    r#"
+        import './styles.css';
+
+        function _matcher(value) {
+            return value;
+        }
+        const Component = () => <div className="container">{_matcher('x')}</div>;
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+        function _matcher1(value) {
+            return value;
+        }
+        const Component = () => <div className={_matcher("container")}>{_matcher1('x')}</div>;
+    "#
+);
+
+// test_generated_style_binding_collision
+test_inline!(
+    syntax(),
+    |_| {
+        let config = get_config();
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_generated_style_binding_collision,
+    r#"
+        import './styles.css';
+
+        const style_0 = { color: 'red' };
+        const Component = () => <div className="container" style={style_0}>Hello</div>;
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+        const style_01 = { color: 'red' };
+        const Component = () => <div className={_matcher("container")} style={style_01}>Hello</div>;
+    "#
+);
@@ -0,0 +1,121 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{get_config, syntax, visit_mut_pass};
+use crate::visitor::JsxCssModulesVisitor;
+
+// test_interleaved_imports_and_statements
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_interleaved_imports_and_statements,
+    r#"
+        'use client';
+        export * from './types';
+        import React from 'react';
+        const VERSION = '1.0';
+        import './a.css';
+        export { VERSION };
+        import { helper } from './helper';
+
+        const Component = () => <div className="a">{helper(VERSION)}</div>;
+        export default Component;
+    "#,
+    r#"
+        'use client';
+        export * from './types';
+        import React from 'react';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+        const VERSION = '1.0';
+        import style_0 from './a.css';
+        export { VERSION };
+        import { helper } from './helper';
+
+        const Component = () => <div className={_matcher("a")}>{helper(VERSION)}</div>;
+        export default Component;
+    "#
+);
+
+// test_jsx_between_imports
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_jsx_between_imports,
+    r#"
+        import './a.css';
+        render(<div className="app" />, root);
+        import './b.css';
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0, style_1);
+        const _matcher = getMatcher(_styles, 'local');
+        render(<div className={_matcher("app")} />, root);
+        import style_1 from './b.css';
+    "#
+);
+
+// test_directive_prologue_with_style_require
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_directive_prologue_with_style_require,
+    r#"
+        'use client';
+        'use strict';
+        const init = () => {};
+        require('./a.css');
+        init();
+
+        export const Component = () => <div className="a" />;
+    "#,
+    r#"
+        'use client';
+        'use strict';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const init = () => {};
+        const style_0 = require('./a.css');
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+        init();
+
+        export const Component = () => <div className={_matcher("a")} />;
+    "#
+);
+
+// test_entry_without_class_names
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_entry_without_class_names,
+    r#"
+        import './global.css';
+        import { render } from 'react-dom';
+        import App from './App';
+
+        render(<App />, document.getElementById('root'));
+    "#,
+    r#"
+        import './global.css';
+        import { render } from 'react-dom';
+        import App from './App';
+
+        render(<App />, document.getElementById('root'));
+    "#
+);
+
+// test_script_without_class_names
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_script_without_class_names,
+    r#"
+        require('./global.css');
+        module.exports = function () {};
+    "#,
+    r#"
+        require('./global.css');
+        module.exports = function () {};
+    "#
+);
@@ -0,0 +1,127 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{fixture_visitor, get_config, syntax, visit_mut_pass};
+use crate::{Config, ImportStyle, visitor::JsxCssModulesVisitor};
+
+// test_named_import_referenced_classes
+// 无法读取的样式文件不知道其中有哪些导出，回退为命名空间导入
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            import_style: ImportStyle::Named,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_named_import_referenced_classes,
+    r#"
+        import cx from 'classnames';
+        import './a.css';
+        import * as theme from './theme.css';
+
+        const Component = () => (
+            <div className="a is-open :global(g)">
+                <span className={cx('b', { a: true })} />
+            </div>
+        );
+    "#,
+    r#"
+        import cx from 'classnames';
+        import * as style_0 from './a.css';
+        import * as theme from './theme.css';
+        const _styles = {
+            a: theme.a || style_0.a,
+            b: theme.b || style_0.b,
+            "is-open": theme["is-open"] || style_0["is-open"]
+        };
+
+        const Component = () =>
+            <div className={`${_styles.a || "a"} ${_styles["is-open"] || "is-open"} g`}>
+                <span className={cx(_styles.b || "b", { [_styles.a || "a"]: true })} />
+            </div>;
+    "#
+);
+
+// test_named_import_falls_back_to_namespace
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            import_style: ImportStyle::Named,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_named_import_falls_back_to_namespace,
+    r#"
+        import './a.css';
+
+        const Component = ({ name }) => <div className="a"><span className={name} /></div>;
+    "#,
+    r#"
+        import * as style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = ({ name }) => <div className={_styles.a || "a"}><span className={_matcher(name)} /></div>;
+    "#
+);
+
+// test_named_import_existing_classes_only
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            import_style: ImportStyle::Named,
+            ..get_config()
+        };
+        visit_mut_pass(fixture_visitor(config))
+    },
+    test_named_import_existing_classes_only,
+    r#"
+        import './App.module.css';
+        import './Button.module.scss';
+
+        const Component = () => <div className="container missing"><button className="button" /></div>;
+    "#,
+    r#"
+        import { container as _container } from './App.module.css';
+        import { button as _button } from './Button.module.scss';
+        const _styles = {
+            button: _button,
+            container: _container
+        };
+
+        const Component = () => <div className={`${_styles.container || "container"} ${_styles.missing || "missing"}`}><button className={_styles.button || "button"} /></div>;
+    "#
+);
+
+// test_named_import_mixed_stylesheets
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            import_style: ImportStyle::Named,
+            ..get_config()
+        };
+        visit_mut_pass(fixture_visitor(config))
+    },
+    test_named_import_mixed_stylesheets,
+    r#"
+        import './App.module.css';
+        import 'ui/theme.css';
+
+        const Component = () => <div className="container title" />;
+    "#,
+    r#"
+        import { container as _container, title as _title } from './App.module.css';
+        import * as style_1 from 'ui/theme.css';
+        const _styles = {
+            container: style_1.container || _container,
+            title: style_1.title || _title
+        };
+
+        const Component = () => <div className={`${_styles.container || "container"} ${_styles.title || "title"}`} />;
+    "#
+);
@@ -0,0 +1,63 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{get_config, syntax, visit_mut_pass};
+use crate::visitor::JsxCssModulesVisitor;
+
+// test_jsx_in_attribute_values
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_jsx_in_attribute_values,
+    r#"
+        import './styles.css';
+
+        const Page = () => (
+            <Layout
+                header={<div className="hd">Header</div>}
+                footer={visible ? <div className="ft">Footer</div> : null}
+                renderItem={(item) => <span className="item">{item}</span>}
+            />
+        );
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Page = () =>
+            <Layout
+                header={<div className={_matcher("hd")}>Header</div>}
+                footer={visible ? <div className={_matcher("ft")}>Footer</div> : null}
+                renderItem={(item) => <span className={_matcher("item")}>{item}</span>}
+            />;
+    "#
+);
+
+// test_jsx_in_spread_and_class_name_expressions
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_jsx_in_spread_and_class_name_expressions,
+    r#"
+        import './styles.css';
+
+        const Page = () => (
+            <Button
+                {...{ icon: <i className="icon" /> }}
+                className={getClassName(<b className="bold" />)}
+            />
+        );
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Page = () =>
+            <Button
+                {...{ icon: <i className={_matcher("icon")} /> }}
+                className={_matcher(getClassName(<b className={_matcher("bold")} />))}
+            />;
+    "#
+);
@@ -0,0 +1,108 @@
+use swc_core::{
+    common::{
+        comments::{Comment, CommentKind},
+        Span,
+    },
+    ecma::transforms::testing::test_inline,
+};
+use super::{get_config, syntax, visit_mut_pass};
+use crate::{pragma::parse_file_pragmas, visitor::JsxCssModulesVisitor, Prefer};
+
+fn block_comment(text: &str) -> Comment {
+    Comment {
+        kind: CommentKind::Block,
+        span: Span::default(),
+        text: text.into(),
+    }
+}
+
+#[test]
+fn test_parse_file_pragmas() {
+    let pragmas = parse_file_pragmas(&[block_comment(" @jsx-css-modules-disable ")]);
+    assert!(pragmas.disabled);
+
+    let pragmas = parse_file_pragmas(&[block_comment("*\n * @jsx-css-modules prefer=strict-local\n ")]);
+    assert!(!pragmas.disabled);
+    assert_eq!(pragmas.prefer, Some(Prefer::StrictLocal));
+
+    let pragmas = parse_file_pragmas(&[block_comment(" @jsx-css-modules-disabled-for-now ")]);
+    assert_eq!(pragmas, Default::default());
+}
+
+// test_file_disable_pragma
+test_inline!(
+    syntax(),
+    |t| visit_mut_pass(JsxCssModulesVisitor::new(get_config()).with_comments(t.comments.clone())),
+    test_file_disable_pragma,
+    r#"
+        /* @jsx-css-modules-disable */
+        import './a.css';
+
+        const Component = () => <div className="a" />;
+    "#,
+    r#"
+        /* @jsx-css-modules-disable */
+        import './a.css';
+
+        const Component = () => <div className="a" />;
+    "#
+);
+
+// test_file_prefer_pragma
+test_inline!(
+    syntax(),
+    |t| visit_mut_pass(JsxCssModulesVisitor::new(get_config()).with_comments(t.comments.clone())),
+    test_file_prefer_pragma,
+    r#"
+        /**
+         * @jsx-css-modules prefer=global
+         */
+        import './a.css';
+
+        const Component = () => <div className="a" />;
+    "#,
+    r#"
+        /**
+         * @jsx-css-modules prefer=global
+         */
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'global');
+
+        const Component = () => <div className={_matcher("a")} />;
+    "#
+);
+
+// test_ignore_next_line_pragma
+test_inline!(
+    syntax(),
+    |t| visit_mut_pass(JsxCssModulesVisitor::new(get_config()).with_comments(t.comments.clone())),
+    test_ignore_next_line_pragma,
+    r#"
+        import './a.css';
+
+        const Component = () => (
+            // jsx-css-modules-ignore-next-line
+            <div className="third-party">
+                {/* jsx-css-modules-ignore-next-line */}
+                <span className="raw" />
+                <span className="a" />
+            </div>
+        );
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = () =>
+            // jsx-css-modules-ignore-next-line
+            <div className="third-party">
+                {/* jsx-css-modules-ignore-next-line */}
+                <span className="raw" />
+                <span className={_matcher("a")} />
+            </div>;
+    "#
+);
@@ -0,0 +1,117 @@
+use swc_core::{common::errors::Level, ecma::transforms::testing::test_inline};
+use super::{collect_diagnostics, fixture_visitor, get_config, syntax, visit_mut_pass};
+use crate::{Config, MissingClass, visitor::JsxCssModulesVisitor};
+
+// test_scopes_runtime_argument
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(get_config())),
+    test_scopes_runtime_argument,
+    r#"
+        import './App.module.css';
+        import btn from './Button.module.scss';
+
+        const Component = ({ cls }) => <div className={cls}><button className=":btn(button) container" /></div>;
+    "#,
+    r#"
+        import style_0 from './App.module.css';
+        import btn from './Button.module.scss';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0, btn);
+        const _matcher = getMatcher(_styles, 'local', {
+            btn
+        });
+
+        const Component = ({ cls }) => <div className={_matcher(cls)}><button className={_matcher(":btn(button) container")} /></div>;
+    "#
+);
+
+// test_scopes_static_resolve
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(Config {
+        static_resolve: true,
+        ..get_config()
+    })),
+    test_scopes_static_resolve,
+    r#"
+        import styles from './App.module.css';
+        import btn from './Button.module.scss';
+
+        const Component = () => <button className=":btn(button icon) container :global(app)" />;
+    "#,
+    r#"
+        import styles from './App.module.css';
+        import btn from './Button.module.scss';
+        const _styles = Object.assign({}, styles, btn);
+
+        const Component = () => <button className={`${_styles.container || "container"} ${btn.button || "button"} ${btn.icon || "icon"} app`} />;
+    "#
+);
+
+// test_scopes_local_ident
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(fixture_visitor(Config {
+        local_ident_name: Some("[name]__[local]".to_string()),
+        ..get_config()
+    })),
+    test_scopes_local_ident,
+    r#"
+        import './App.module.css';
+        import card from './Card.module.css';
+        import btn from './Button.module.scss';
+
+        const Component = () => <div className="container :card(button)"><button className=":btn(button)" /></div>;
+    "#,
+    r#"
+        import './App.module.css';
+        import card from './Card.module.css';
+        import btn from './Button.module.scss';
+
+        const Component = () => <div className="App-module__container Card-module__button"><button className="Button-module__button" /></div>;
+    "#
+);
+
+#[test]
+fn test_ambiguous_class_warning() {
+    let source = r#"
+import btn from './Button.module.scss';
+import card from './Card.module.css';
+
+const Component = () => (
+    <div className="card">
+        <button className="button">Click me</button>
+        <button className=":card(button)">Click me</button>
+    </div>
+);
+"#;
+    let diagnostics = collect_diagnostics(fixture_visitor(get_config()), source);
+    assert_eq!(diagnostics.len(), 1);
+
+    let (diagnostic, line) = &diagnostics[0];
+    assert_eq!(diagnostic.level, Level::Warning);
+    assert_eq!(*line, 7);
+    assert_eq!(
+        diagnostic.message(),
+        "class `button` is defined in multiple imported stylesheets (`./Button.module.scss`, `./Card.module.css`), the last one wins"
+    );
+    assert_eq!(diagnostic.children[0].message(), "use `:btn(button)` or `:card(button)` to pick one");
+}
+
+#[test]
+fn test_scoped_missing_class() {
+    let source = r#"
+import btn from './Button.module.scss';
+import card from './Card.module.css';
+
+const Component = () => <div className=":btn(card)" />;
+"#;
+    let config = Config {
+        missing_class: MissingClass::Warn,
+        ..get_config()
+    };
+    let diagnostics = collect_diagnostics(fixture_visitor(config), source);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].0.message(), "class `card` does not exist in the imported stylesheets");
+}
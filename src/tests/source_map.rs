@@ -0,0 +1,82 @@
+use swc_core::{
+    common::{sync::Lrc, FileName, SourceMap, GLOBALS},
+    ecma::{
+        ast::*,
+        codegen::{text_writer::JsWriter, Emitter},
+        parser::parse_file_as_module,
+        visit::VisitMutWith,
+    },
+};
+use super::{get_config, syntax};
+use crate::{visitor::JsxCssModulesVisitor, Config};
+
+const SOURCE: &str = r#"import './a.css';
+
+const Component = () => (
+    <div className="container">
+        <span className={active ? "on" : "off"} />
+    </div>
+);
+"#;
+
+// 转换并输出代码，返回生成代码与对应的 source map
+fn transform_with_source_map(config: Config) -> (String, sourcemap::SourceMap) {
+    GLOBALS.set(&Default::default(), || {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(Lrc::new(FileName::Custom("App.jsx".into())), SOURCE.to_string());
+        let mut module = parse_file_as_module(&fm, syntax(), EsVersion::latest(), None, &mut vec![]).unwrap();
+        module.visit_mut_with(&mut JsxCssModulesVisitor::new(config));
+
+        let mut code = vec![];
+        let mut mappings = vec![];
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            cm: cm.clone(),
+            comments: None,
+            wr: JsWriter::new(cm.clone(), "\n", &mut code, Some(&mut mappings)),
+        };
+        emitter.emit_module(&module).unwrap();
+
+        (String::from_utf8(code).unwrap(), cm.build_source_map(&mappings))
+    })
+}
+
+// 返回生成代码中 `needle` 所在位置映射回的原始 (行, 列)，均从 0 开始
+fn original_position(code: &str, source_map: &sourcemap::SourceMap, needle: &str) -> (u32, u32) {
+    let offset = code.find(needle).unwrap_or_else(|| panic!("`{}` not found in:\n{}", needle, code));
+    let line = code[..offset].matches('\n').count() as u32;
+    let col = (offset - code[..offset].rfind('\n').map_or(0, |i| i + 1)) as u32;
+    let token = source_map.lookup_token(line, col).unwrap();
+    (token.get_src_line(), token.get_src_col())
+}
+
+#[test]
+fn test_matcher_call_maps_to_attribute() {
+    let (code, source_map) = transform_with_source_map(get_config());
+    assert_eq!(original_position(&code, &source_map, r#"_matcher("container")"#), (3, 19));
+    assert_eq!(original_position(&code, &source_map, r#"_matcher("on")"#), (4, 34));
+}
+
+#[test]
+fn test_static_class_names_map_to_attribute() {
+    let config = Config {
+        static_resolve: true,
+        ..get_config()
+    };
+    let (code, source_map) = transform_with_source_map(config);
+    assert_eq!(original_position(&code, &source_map, r#"_styles.container || "container""#), (3, 19));
+}
+
+#[test]
+fn test_style_binding_maps_to_import() {
+    let (code, source_map) = transform_with_source_map(get_config());
+    assert_eq!(original_position(&code, &source_map, "import style_0"), (0, 0));
+}
+
+#[test]
+fn test_runtime_maps_to_first_style_import() {
+    let (code, source_map) = transform_with_source_map(get_config());
+    assert_eq!(original_position(&code, &source_map, "import { getMatcher }"), (0, 0));
+    assert_eq!(original_position(&code, &source_map, "const _styles"), (0, 0));
+    assert_eq!(original_position(&code, &source_map, "const _matcher"), (0, 0));
+}
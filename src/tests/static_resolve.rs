@@ -0,0 +1,139 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{get_config, syntax, visit_mut_pass};
+use crate::{class_names::parse_class_names, Config, Prefer, visitor::JsxCssModulesVisitor};
+
+#[test]
+fn test_parse_class_names() {
+    let class_names = parse_class_names("a :global(b) c", Prefer::Local, &[]);
+    assert_eq!(class_names.locals, vec!["a", "c"]);
+    assert_eq!(class_names.globals, vec!["b"]);
+
+    let class_names = parse_class_names(":local(a)  b :global(c d)", Prefer::Global, &[]);
+    assert_eq!(class_names.locals, vec!["a"]);
+    assert_eq!(class_names.globals, vec!["c", "d", "b"]);
+
+    // 与 getMatcher 中的非贪婪正则一致：遇到第一个右括号即结束，未闭合的标记按普通类名处理
+    let class_names = parse_class_names(":global(a :global(b) c :local(d", Prefer::Local, &[]);
+    assert_eq!(class_names.globals, vec!["a", "b"]);
+    assert_eq!(class_names.locals, vec!["c", ":local(d"]);
+}
+
+// test_static_mixed_classes
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            static_resolve: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_static_mixed_classes,
+    r#"
+        import './styles.css';
+        const Component = () => <div className="a :global(b) c">Hello</div>;
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        const _styles = Object.assign({}, style_0);
+        const Component = () => <div className={`${_styles.a || "a"} ${_styles.c || "c"} b`}>Hello</div>;
+    "#
+);
+
+// test_static_single_local_class
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            static_resolve: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_static_single_local_class,
+    r#"
+        import './styles.css';
+        const Component = () => <div className="btn-primary">
+            <span className=":local(text)">Hello</span>
+        </div>;
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        const _styles = Object.assign({}, style_0);
+        const Component = () => <div className={_styles["btn-primary"] || "btn-primary"}>
+            <span className={_styles.text || "text"}>Hello</span>
+        </div>;
+    "#
+);
+
+// test_static_global_only
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            prefer: Prefer::Global,
+            static_resolve: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_static_global_only,
+    r#"
+        import './styles.css';
+        const Component = () => <div className="container  :global(wrapper)">
+            <span className="text :local(bold)">Hello</span>
+        </div>;
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        const _styles = Object.assign({}, style_0);
+        const Component = () => <div className="wrapper container">
+            <span className={`${_styles.bold || "bold"} text`}>Hello</span>
+        </div>;
+    "#
+);
+
+// test_static_keeps_dynamic_expressions
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            static_resolve: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_static_keeps_dynamic_expressions,
+    r#"
+        import './styles.css';
+        const Component = () => <div className={`container ${active}`}>Hello</div>;
+    "#,
+    r#"
+        import style_0 from './styles.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+        const Component = () => <div className={_matcher(`container ${active}`)}>Hello</div>;
+    "#
+);
+
+// test_static_global_classes_keep_import
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            static_resolve: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_static_global_classes_keep_import,
+    r#"
+        import './styles.css';
+        const Component = () => <div className=":global(app  shell)">Hello</div>;
+    "#,
+    r#"
+        import './styles.css';
+        const Component = () => <div className="app shell">Hello</div>;
+    "#
+);
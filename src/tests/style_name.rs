@@ -0,0 +1,240 @@
+use swc_core::{common::errors::Level, ecma::transforms::testing::test_inline};
+use super::{collect_diagnostics, get_config, syntax, visit_mut_pass};
+use crate::{visitor::JsxCssModulesVisitor, Config, Prefer};
+
+// test_style_name_without_class_name
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            style_name: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_style_name_without_class_name,
+    r#"
+        import './a.css';
+
+        const Component = () => <div id="app" styleName="a :global(b)" />;
+    "#,
+    r#"
+        import style_0 from './a.css';
+        const _styles = Object.assign({}, style_0);
+
+        const Component = () => <div id="app" className={`${_styles.a || "a"} b`} />;
+    "#
+);
+
+// test_style_name_with_string_class_name
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            style_name: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_style_name_with_string_class_name,
+    r#"
+        import './a.css';
+
+        const Component = () => <div className="global-x" styleName="a" />;
+    "#,
+    r#"
+        import style_0 from './a.css';
+        const _styles = Object.assign({}, style_0);
+
+        const Component = () => <div className={`global-x ${_styles.a || "a"}`} />;
+    "#
+);
+
+// test_style_name_with_expression_class_name
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            style_name: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_style_name_with_expression_class_name,
+    r#"
+        import './a.css';
+
+        const Component = ({ className }) => <div className={className} styleName="a b" />;
+    "#,
+    r#"
+        import style_0 from './a.css';
+        const _styles = Object.assign({}, style_0);
+
+        const Component = ({ className }) =>
+            <div className={[className, `${_styles.a || "a"} ${_styles.b || "b"}`].filter(Boolean).join(" ")} />;
+    "#
+);
+
+// test_dynamic_style_name
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            style_name: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_dynamic_style_name,
+    r#"
+        import './a.css';
+
+        const Component = ({ active, name }) => (
+            <div className="global-x" styleName={active ? "a" : name} />
+        );
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = ({ active, name }) =>
+            <div className={["global-x", active ? _styles.a || "a" : _matcher(name)].filter(Boolean).join(" ")} />;
+    "#
+);
+
+// test_dynamic_style_name_with_global_prefer
+// styleName 总是局部类名，文件的 prefer 为 global 时动态值交给单独的局部 matcher
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(Config {
+        prefer: Prefer::Global,
+        attributes: vec!["className".into(), "*ClassName".into()],
+        style_name: true,
+        ..get_config()
+    })),
+    test_dynamic_style_name_with_global_prefer,
+    r#"
+        import './a.css';
+
+        const Component = ({ active, name, cls }) => (
+            <div wrapperClassName={cls}>
+                <span styleName={active ? "a" : name} />
+            </div>
+        );
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'global');
+        const _localMatcher = getMatcher(_styles, 'local');
+
+        const Component = ({ active, name, cls }) =>
+            <div wrapperClassName={_matcher(cls)}>
+                <span className={active ? _styles.a || "a" : _localMatcher(name)} />
+            </div>;
+    "#
+);
+
+// test_nullish_style_name
+// 动态 styleName 可能为 undefined/null，不能直接拼进模板字符串
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            style_name: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_nullish_style_name,
+    r#"
+        import './a.css';
+
+        const Component = ({ name }) => (
+            <div>
+                <span className="g" styleName={name} />
+                <span className="g" styleName={undefined} />
+                <span className="g" styleName={null} />
+            </div>
+        );
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'local');
+
+        const Component = ({ name }) =>
+            <div>
+                <span className={["g", _matcher(name)].filter(Boolean).join(" ")} />
+                <span className={["g", undefined].filter(Boolean).join(" ")} />
+                <span className={["g", null].filter(Boolean).join(" ")} />
+            </div>;
+    "#
+);
+
+// test_style_name_keeps_class_name_global
+test_inline!(
+    syntax(),
+    |_| visit_mut_pass(JsxCssModulesVisitor::new(Config {
+        prefer: Prefer::Global,
+        attributes: vec!["className".into(), "*ClassName".into()],
+        style_name: true,
+        ..get_config()
+    })),
+    test_style_name_keeps_class_name_global,
+    r#"
+        import './a.css';
+
+        const Component = ({ cls }) => (
+            <div>
+                <span className="a" wrapperClassName="b" styleName="c" />
+                <span className="d" />
+                <span className={cls} />
+            </div>
+        );
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0);
+        const _matcher = getMatcher(_styles, 'global');
+
+        const Component = ({ cls }) =>
+            <div>
+                <span className={`a ${_styles.c || "c"}`} wrapperClassName={_matcher("b")} />
+                <span className="d" />
+                <span className={cls} />
+            </div>;
+    "#
+);
+
+#[test]
+fn test_style_name_rejects_arrays_and_objects() {
+    let source = r#"
+import './a.css';
+
+const Component = () => (
+    <div>
+        <span className="g" styleName={["a", "b"]} />
+        <span styleName={{ a: true }} />
+    </div>
+);
+"#;
+    let config = Config {
+        style_name: true,
+        ..get_config()
+    };
+    let diagnostics = collect_diagnostics(JsxCssModulesVisitor::new(config), source);
+    assert_eq!(diagnostics.len(), 2);
+
+    let (diagnostic, line) = &diagnostics[0];
+    assert_eq!(diagnostic.level, Level::Error);
+    assert_eq!(*line, 6);
+    assert_eq!(diagnostic.message(), "`styleName` only accepts strings");
+    assert_eq!(diagnostic.children[0].message(), "use `className` with classnames or clsx for arrays and objects");
+    assert_eq!(diagnostics[1].1, 7);
+}
@@ -0,0 +1,107 @@
+use swc_core::ecma::transforms::testing::test_inline;
+use super::{get_config, syntax, visit_mut_pass};
+use crate::{usage::ClassUsage, visitor::JsxCssModulesVisitor, Config, UsageManifest};
+
+#[test]
+fn test_class_usage_json() {
+    let mut usage = ClassUsage::new(&["./a.css".to_string()]);
+    usage.locals.extend(["b".to_string(), "a".to_string()]);
+    usage.globals.insert("app".to_string());
+    assert_eq!(
+        usage.to_json(),
+        r#"{"stylesheets":["./a.css"],"locals":["a","b"],"scoped":{},"globals":["app"]}"#
+    );
+}
+
+// test_usage_manifest_export
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            usage_manifest: UsageManifest::Export,
+            static_resolve: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_usage_manifest_export,
+    r#"
+        import './a.css';
+        import btn from './button.css';
+
+        const Component = ({ cls }) => (
+            <div className="container :global(app) container">
+                <button className=":btn(primary) :local(icon)" />
+                <span className={cls} />
+            </div>
+        );
+    "#,
+    r#"
+        import style_0 from './a.css';
+        import btn from './button.css';
+        import { getMatcher } from 'swc-plugin-jsx-css-modules/helpers';
+        const _styles = Object.assign({}, style_0, btn);
+        const _matcher = getMatcher(_styles, 'local', {
+            btn
+        });
+
+        const Component = ({ cls }) => <div className={`${_styles.container || "container"} ${_styles.container || "container"} app`}>
+                <button className={`${_styles.icon || "icon"} ${btn.primary || "primary"}`} />
+                <span className={_matcher(cls)} />
+            </div>;
+        export const __cssModulesUsage = {
+            globals: ["app"],
+            locals: ["container", "icon"],
+            scoped: {
+                "./button.css": ["primary"]
+            },
+            stylesheets: ["./a.css", "./button.css"]
+        };
+    "#
+);
+
+// test_usage_manifest_comment
+test_inline!(
+    syntax(),
+    |t| {
+        let config = Config {
+            usage_manifest: UsageManifest::Comment,
+            static_resolve: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config).with_comments(t.comments.clone()))
+    },
+    test_usage_manifest_comment,
+    r#"
+        import './a.css';
+
+        const Component = () => <div className="a :global(b)" />;
+    "#,
+    r#"
+        /* @jsx-css-modules-usage {"stylesheets":["./a.css"],"locals":["a"],"scoped":{},"globals":["b"]} */
+        import style_0 from './a.css';
+        const _styles = Object.assign({}, style_0);
+
+        const Component = () => <div className={`${_styles.a || "a"} b`} />;
+    "#
+);
+
+// test_usage_manifest_without_style_import
+test_inline!(
+    syntax(),
+    |_| {
+        let config = Config {
+            usage_manifest: UsageManifest::Export,
+            static_resolve: true,
+            ..get_config()
+        };
+        visit_mut_pass(JsxCssModulesVisitor::new(config))
+    },
+    test_usage_manifest_without_style_import,
+    r#"
+        const Component = () => <div className="a" />;
+    "#,
+    r#"
+        const Component = () => <div className="a" />;
+    "#
+);
@@ -0,0 +1,105 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use serde_json::Value;
+use swc_core::{
+    common::{Span, SyntaxContext},
+    ecma::ast::*,
+};
+
+pub const USAGE_EXPORT: &str = "__cssModulesUsage";
+pub const USAGE_COMMENT: &str = "@jsx-css-modules-usage";
+
+/// 单个文件匹配到的样式导入，以及字面量类名中引用到的局部/全局类名
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct ClassUsage {
+    pub stylesheets: Vec<String>,
+    pub locals: BTreeSet<String>,
+    // 样式导入路径 -> 通过 `:name(...)` 引用的类名
+    pub scoped: BTreeMap<String, BTreeSet<String>>,
+    pub globals: BTreeSet<String>,
+}
+
+impl ClassUsage {
+    pub fn new(stylesheets: &[String]) -> Self {
+        Self {
+            stylesheets: stylesheets.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// 注释内容，如 ` @jsx-css-modules-usage {"stylesheets":["./a.css"],...} `
+    pub fn to_comment_text(&self) -> String {
+        format!(" {} {} ", USAGE_COMMENT, self.to_json())
+    }
+
+    /// `export const __cssModulesUsage = { ... }`
+    pub fn to_export_item(&self) -> ModuleItem {
+        let value = serde_json::to_value(self).unwrap();
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            span: Span::default(),
+            decl: Decl::Var(Box::new(VarDecl {
+                span: Span::default(),
+                kind: VarDeclKind::Const,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span: Span::default(),
+                    name: Pat::Ident(BindingIdent {
+                        id: Ident::new_no_ctxt(USAGE_EXPORT.into(), Span::default()),
+                        type_ann: None,
+                    }),
+                    init: Some(Box::new(json_to_expr(&value))),
+                    definite: false,
+                }],
+                ctxt: SyntaxContext::default(),
+            })),
+        }))
+    }
+}
+
+fn is_ident_name(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+// 清单中只包含对象、数组与字符串
+fn json_to_expr(value: &Value) -> Expr {
+    match value {
+        Value::Object(map) => Expr::Object(ObjectLit {
+            span: Span::default(),
+            props: map
+                .iter()
+                .map(|(key, value)| {
+                    let key = if is_ident_name(key) {
+                        PropName::Ident(IdentName::new(key.as_str().into(), Span::default()))
+                    } else {
+                        PropName::Str(key.as_str().into())
+                    };
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key,
+                        value: Box::new(json_to_expr(value)),
+                    })))
+                })
+                .collect(),
+        }),
+        Value::Array(values) => Expr::Array(ArrayLit {
+            span: Span::default(),
+            elems: values
+                .iter()
+                .map(|value| {
+                    Some(ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(json_to_expr(value)),
+                    })
+                })
+                .collect(),
+        }),
+        Value::String(value) => Expr::Lit(Lit::Str(value.as_str().into())),
+        value => unreachable!("unexpected value in class usage manifest: {}", value),
+    }
+}
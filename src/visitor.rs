@@ -1,62 +1,1141 @@
 use swc_core::ecma::{
     ast::*,
-    visit::{Fold, FoldWith},
+    utils::private_ident,
+    visit::{VisitMut, VisitMutWith},
 };
-use swc_core::common::{Span, SyntaxContext};
-use regex::Regex;
-use super::Config;
+use swc_core::common::{
+    comments::{Comment, CommentKind, Comments},
+    errors::HANDLER,
+    util::take::Take,
+    BytePos, Span, Spanned, SyntaxContext,
+};
+use glob::{MatchOptions, Pattern};
+use regex::{Regex, RegexSet};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+use super::{
+    class_names::parse_class_names,
+    inline_helper::create_inline_get_matcher,
+    local_ident::generate_local_ident,
+    pragma::{has_ignore_next_line, parse_file_pragmas},
+    stylesheet::Stylesheet,
+    usage::ClassUsage,
+    ClassAttribute, Config, Elements, Helper, ImportStyle, MissingClass, Prefer, UsageManifest,
+};
+
+const HELPERS_MODULE: &str = "swc-plugin-jsx-css-modules/helpers";
+const DEFAULT_STYLE_FILE_REG: &str = r"\.(css|scss|sass|less)$";
+const STYLE_NAME: &str = "styleName";
+
+// 顶层的样式 require 语句
+struct StyleRequire {
+    src: Str,
+    binding: Option<Ident>,
+}
+
+fn create_require_call(src: Str) -> Expr {
+    Expr::Call(CallExpr {
+        span: src.span,
+        callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
+            "require".into(),
+            src.span,
+            SyntaxContext::default(),
+        )))),
+        args: vec![ExprOrSpread {
+            spread: None,
+            expr: Box::new(Expr::Lit(Lit::Str(src))),
+        }],
+        type_args: None,
+        ctxt: SyntaxContext::default(),
+    })
+}
+
+// 匹配 `require('xxx')` 调用，返回模块路径
+fn match_require_call(expr: &Expr) -> Option<&Str> {
+    let Expr::Call(CallExpr { callee: Callee::Expr(callee), args, .. }) = expr else {
+        return None;
+    };
+    let Expr::Ident(ident) = &**callee else {
+        return None;
+    };
+    if ident.sym != *"require" || args.len() != 1 || args[0].spread.is_some() {
+        return None;
+    }
+    match &*args[0].expr {
+        Expr::Lit(Lit::Str(src)) => Some(src),
+        _ => None,
+    }
+}
+
+// 与 JSX 的编译规则一致：小写开头的标签与 `svg:rect` 这类命名空间标签为 DOM 元素，其余为组件
+fn is_component(name: &JSXElementName) -> bool {
+    match name {
+        JSXElementName::Ident(ident) => !ident.sym.starts_with(|c: char| c.is_ascii_lowercase()),
+        JSXElementName::JSXMemberExpr(_) => true,
+        JSXElementName::JSXNamespacedName(_) => false,
+    }
+}
+
+// 元素名的字符串形式，如 `div`、`Foo.Bar`、`svg:rect`
+fn element_name(name: &JSXElementName) -> String {
+    fn object_name(object: &JSXObject) -> String {
+        match object {
+            JSXObject::Ident(ident) => ident.sym.to_string(),
+            JSXObject::JSXMemberExpr(member) => format!("{}.{}", object_name(&member.obj), member.prop.sym),
+        }
+    }
+    match name {
+        JSXElementName::Ident(ident) => ident.sym.to_string(),
+        JSXElementName::JSXMemberExpr(member) => format!("{}.{}", object_name(&member.obj), member.prop.sym),
+        JSXElementName::JSXNamespacedName(name) => format!("{}:{}", name.ns.sym, name.name.sym),
+    }
+}
+
+// 由静态片段与插值表达式创建模板字符串，quasis 比 exprs 多一个
+fn create_tpl(quasis: Vec<String>, exprs: Vec<Expr>, span: Span) -> Expr {
+    let last_index = quasis.len() - 1;
+    Expr::Tpl(Tpl {
+        span,
+        exprs: exprs.into_iter().map(Box::new).collect(),
+        quasis: quasis
+            .into_iter()
+            .enumerate()
+            .map(|(i, quasi)| TplElement {
+                span,
+                tail: i == last_index,
+                cooked: Some(quasi.as_str().into()),
+                raw: quasi
+                    .replace('\\', "\\\\")
+                    .replace('`', "\\`")
+                    .replace("${", "\\${")
+                    .into(),
+            })
+            .collect(),
+    })
+}
+
+// 字符串结果直接作为属性值，其余表达式放入 `{...}`
+fn create_attr_value(expr: Expr, span: Span) -> JSXAttrValue {
+    match expr {
+        Expr::Lit(Lit::Str(str_lit)) => JSXAttrValue::Lit(Lit::Str(str_lit)),
+        expr => JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            span,
+            expr: JSXExpr::Expr(Box::new(expr)),
+        }),
+    }
+}
+
+fn is_attr_named(attr: &JSXAttrOrSpread, name: &str) -> bool {
+    matches!(attr, JSXAttrOrSpread::JSXAttr(JSXAttr { name: JSXAttrName::Ident(ident), .. }) if ident.sym == *name)
+}
+
+// 将映射后的 styleName 与原有的 className 合并。字面量 styleName 的映射结果总是字符串，可以与字符串 className
+// 直接拼接；动态 styleName 可能为 undefined/null，与表达式 className 一样使用
+// `[className, styleName].filter(Boolean).join(" ")`，避免输出 "undefined" 且 className 只求值一次
+fn merge_style_name(class_name: Option<JSXAttrValue>, style_name: Expr, static_style_name: bool, span: Span) -> Expr {
+    let class_name = match class_name {
+        Some(JSXAttrValue::Lit(Lit::Str(class_name))) if !class_name.value.trim().is_empty() => {
+            let class_name = class_name.value.trim().to_string();
+            match style_name {
+                Expr::Lit(Lit::Str(style_name)) => {
+                    return Expr::Lit(Lit::Str(Str {
+                        span,
+                        value: format!("{} {}", class_name, style_name.value).into(),
+                        raw: None,
+                    }))
+                }
+                style_name if static_style_name => {
+                    return create_tpl(vec![format!("{} ", class_name), String::new()], vec![style_name], span)
+                }
+                _ => Box::new(Expr::Lit(Lit::Str(Str {
+                    span,
+                    value: class_name.into(),
+                    raw: None,
+                }))),
+            }
+        }
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(class_name),
+            ..
+        })) => class_name,
+        _ => return style_name,
+    };
+
+    let array = Expr::Array(ArrayLit {
+        span,
+        elems: vec![
+            Some(ExprOrSpread { spread: None, expr: class_name }),
+            Some(ExprOrSpread { spread: None, expr: Box::new(style_name) }),
+        ],
+    });
+    let filter = Expr::Call(CallExpr {
+        span,
+        callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+            span,
+            obj: Box::new(array),
+            prop: MemberProp::Ident(IdentName::new("filter".into(), span)),
+        }))),
+        args: vec![ExprOrSpread {
+            spread: None,
+            expr: Box::new(Expr::Ident(Ident::new("Boolean".into(), span, SyntaxContext::default()))),
+        }],
+        type_args: None,
+        ctxt: SyntaxContext::default(),
+    });
+    Expr::Call(CallExpr {
+        span,
+        callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+            span,
+            obj: Box::new(filter),
+            prop: MemberProp::Ident(IdentName::new("join".into(), span)),
+        }))),
+        args: vec![ExprOrSpread {
+            spread: None,
+            expr: Box::new(Expr::Lit(Lit::Str(" ".into()))),
+        }],
+        type_args: None,
+        ctxt: SyntaxContext::default(),
+    })
+}
+
+// 复用已有导入的来源，创建只包含给定说明符的导入语句
+fn create_import_item(import: &ImportDecl, specifiers: Vec<ImportSpecifier>) -> ModuleItem {
+    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+        span: import.span,
+        specifiers,
+        src: import.src.clone(),
+        type_only: import.type_only,
+        with: import.with.clone(),
+        phase: import.phase,
+    }))
+}
+
+// 按原始位置插入新节点，同一位置的节点保持传入顺序
+fn insert_items<T>(body: &mut Vec<T>, mut insertions: Vec<(usize, T)>) {
+    insertions.sort_by_key(|(position, _)| *position);
+    for (position, item) in insertions.into_iter().rev() {
+        body.insert(position, item);
+    }
+}
+
+// 通过 `:name(...)` 只在单个样式文件中映射的作用域，name 为该样式导入的绑定名
+struct StyleScope {
+    name: String,
+    index: usize,
+    binding: Ident,
+}
+
+// 静态解析结果中的一个类名：已确定的字符串或运行时查找表达式
+enum ClassNamePart {
+    Static(String),
+    Lookup(Expr),
+}
+
+// 动态类名表达式所处的位置，决定字面量是否在编译期解析、无法分析的值如何处理
+#[derive(Clone, Copy, PartialEq)]
+enum ClassNameSite {
+    // 类名属性的值
+    Attribute,
+    // classnames/clsx 等调用的参数及其中的数组元素，值可能是对象或数组变量
+    Helper,
+    // 计算属性名等一定会转换为字符串的位置
+    String,
+}
+
+// 创建 `styles.xxx || "xxx"` 表达式，span 指向原始的类名字面量
+fn create_lookup_expr(styles: Ident, class_name: &str, span: Span) -> Expr {
+    let prop = if Ident::verify_symbol(class_name).is_ok() {
+        MemberProp::Ident(IdentName::new(class_name.into(), span))
+    } else {
+        MemberProp::Computed(ComputedPropName {
+            span,
+            expr: Box::new(Expr::Lit(Lit::Str(Str {
+                span,
+                value: class_name.into(),
+                raw: None,
+            }))),
+        })
+    };
+
+    Expr::Bin(BinExpr {
+        span,
+        op: BinaryOp::LogicalOr,
+        left: Box::new(Expr::Member(MemberExpr {
+            span,
+            obj: Box::new(Expr::Ident(styles)),
+            prop,
+        })),
+        right: Box::new(Expr::Lit(Lit::Str(Str {
+            span,
+            value: class_name.into(),
+            raw: None,
+        }))),
+    })
+}
+
+// 属性名与元素名的匹配规则：`/.../` 为正则，含 `*` 为通配符，其余为精确匹配
+fn compile_attribute_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    if let Some(regex) = pattern.strip_prefix('/').and_then(|p| p.strip_suffix('/')) {
+        return Regex::new(regex);
+    }
+    let regex = pattern
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(".*");
+    Regex::new(&format!("^{}$", regex))
+}
 
 pub struct JsxCssModulesVisitor {
     config: Config,
+    style_file_regex: RegexSet,
+    style_file_globs: Vec<Pattern>,
+    attribute_patterns: Vec<Regex>,
+    // `elements` 为 { include, exclude } 时编译后的元素名规则
+    element_patterns: Option<(Vec<Regex>, Vec<Regex>)>,
+    filename: Option<String>,
+    cwd: Option<String>,
     styles_ident: Option<Ident>,
     matcher_ident: Option<Ident>,
+    // 文件的 prefer 为 global 时，动态 styleName 使用的局部 matcher，用到时才创建
+    local_matcher_ident: Option<Ident>,
+    get_matcher_ident: Option<Ident>,
+    // 构建期读取到的样式文件，任一文件无法读取时为 None
+    stylesheets: Option<Vec<Stylesheet>>,
+    // 构建期计算得到的 类名 -> 哈希类名 映射
+    local_idents: Option<HashMap<String, String>>,
+    // 从 classNameHelpers 中的模块导入的 classnames/clsx 等函数
+    class_name_helpers: Vec<Id>,
+    // 静态解析时引用到的局部类名，用于 named 导入
+    referenced_class_names: Vec<String>,
+    style_srcs: Vec<String>,
+    style_scopes: Vec<StyleScope>,
+    comments: Option<Box<dyn Comments>>,
+    // 被 `jsx-css-modules-ignore-next-line` 跳过的元素起始位置
+    ignored_elements: HashSet<BytePos>,
+    // 是否需要引入 getMatcher，matcher_used 表示是否调用了 _matcher
+    runtime_used: bool,
+    matcher_used: bool,
+    // 当前文件生效的 prefer，文件开头的 `@jsx-css-modules prefer=...` 指令可以覆盖配置
+    prefer: Prefer,
+    // 第一个匹配的样式导入（或 require）的位置，注入的运行时代码沿用该位置
+    runtime_span: Span,
+    // 当前正在转换的属性的处理方式
+    class_attribute: ClassAttribute,
+    // 开启 usageManifest 时记录的类名使用情况
+    usage: Option<ClassUsage>,
 }
 
 impl JsxCssModulesVisitor {
+    /// 创建转换器，配置中的正则无法编译时 panic，需要处理错误时使用 [`Self::try_new`]
     pub fn new(config: Config) -> Self {
-        Self {
+        Self::try_new(config).unwrap_or_else(|err| panic!("swc-plugin-jsx-css-modules: invalid config: {}", err))
+    }
+
+    /// 预先编译配置中的所有正则，失败时返回包含字段名与取值的错误信息
+    pub fn try_new(config: Config) -> Result<Self, String> {
+        // 未配置 styleFileReg 与 styleFileGlob 时使用默认的样式文件规则
+        let style_file_reg = if config.style_file_reg.is_empty() && config.style_file_glob.is_empty() {
+            vec![DEFAULT_STYLE_FILE_REG.to_string()]
+        } else {
+            config.style_file_reg.clone()
+        };
+        let style_file_regex = RegexSet::new(&style_file_reg).map_err(|err| {
+            let pattern = style_file_reg
+                .iter()
+                .find(|pattern| Regex::new(pattern).is_err())
+                .cloned()
+                .unwrap_or_default();
+            format!(r#"invalid `styleFileReg` pattern "{}": {}"#, pattern, err)
+        })?;
+        let style_file_globs = config
+            .style_file_glob
+            .iter()
+            .map(|pattern| {
+                Pattern::new(pattern)
+                    .map_err(|err| format!(r#"invalid `styleFileGlob` pattern "{}": {}"#, pattern, err))
+            })
+            .collect::<Result<_, _>>()?;
+        let attribute_patterns = config
+            .attributes
+            .iter()
+            .map(|pattern| {
+                compile_attribute_pattern(pattern)
+                    .map_err(|err| format!(r#"invalid `attributes` pattern "{}": {}"#, pattern, err))
+            })
+            .collect::<Result<_, _>>()?;
+        let compile_element_patterns = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    compile_attribute_pattern(pattern)
+                        .map_err(|err| format!(r#"invalid `elements` pattern "{}": {}"#, pattern, err))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let element_patterns = match &config.elements {
+            Elements::Filter { include, exclude } => {
+                Some((compile_element_patterns(include)?, compile_element_patterns(exclude)?))
+            }
+            _ => None,
+        };
+        Ok(Self {
             config,
+            style_file_regex,
+            style_file_globs,
+            attribute_patterns,
+            element_patterns,
+            filename: None,
+            cwd: None,
             styles_ident: None,
             matcher_ident: None,
+            local_matcher_ident: None,
+            get_matcher_ident: None,
+            stylesheets: None,
+            local_idents: None,
+            class_name_helpers: vec![],
+            referenced_class_names: vec![],
+            style_srcs: vec![],
+            style_scopes: vec![],
+            comments: None,
+            ignored_elements: HashSet::new(),
+            runtime_used: false,
+            matcher_used: false,
+            prefer: Prefer::default(),
+            runtime_span: Span::default(),
+            class_attribute: ClassAttribute::STRING,
+            usage: None,
+        })
+    }
+
+    /// 当前转换的文件路径，用于定位相对路径导入的样式文件
+    pub fn with_filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = Some(filename.into());
+        self
+    }
+
+    /// 源码中的注释，用于读取 `@jsx-css-modules-disable` 等指令
+    pub fn with_comments(mut self, comments: impl Comments + 'static) -> Self {
+        self.comments = Some(Box::new(comments));
+        self
+    }
+
+    /// 工作目录，作为 `localIdentName` 中路径与哈希的计算基准
+    pub fn with_cwd(mut self, cwd: impl Into<String>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    fn is_style_file(&self, src: &str) -> bool {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        self.style_file_regex.is_match(src)
+            || self.style_file_globs.iter().any(|pattern| pattern.matches_with(src, options))
+    }
+
+    fn is_element_included(&self, name: &JSXElementName) -> bool {
+        match (&self.config.elements, &self.element_patterns) {
+            (Elements::Intrinsic, _) => !is_component(name),
+            (Elements::Filter { .. }, Some((include, exclude))) => {
+                let name = element_name(name);
+                (include.is_empty() || include.iter().any(|pattern| pattern.is_match(&name)))
+                    && !exclude.iter().any(|pattern| pattern.is_match(&name))
+            }
+            _ => true,
+        }
+    }
+
+    // 框架预设中的属性优先，其次是 `attributes` 配置；返回 None 表示该属性不需要转换
+    fn class_attribute(&self, name: &JSXAttrName, is_component: bool) -> Option<ClassAttribute> {
+        let name = match name {
+            JSXAttrName::Ident(ident) => ident.sym.to_string(),
+            JSXAttrName::JSXNamespacedName(name) => format!("{}:{}", name.ns.sym, name.name.sym),
+        };
+        if let Some((_, attribute)) = self.config.framework.attributes().iter().find(|(preset, _)| *preset == name) {
+            return (attribute.components || !is_component).then_some(*attribute);
         }
+        self.attribute_patterns
+            .iter()
+            .any(|pattern| pattern.is_match(&name))
+            .then_some(ClassAttribute::STRING)
     }
 
     fn is_style_import(&self, import: &ImportDecl) -> bool {
-        for pattern in &self.config.style_file_reg {
-            if let Ok(regex) = Regex::new(pattern) {
-                if regex.is_match(&import.src.value.to_string()) {
-                    return true;
+        self.is_style_file(&import.src.value)
+    }
+
+    // 匹配 `require('./a.css')` 调用，返回样式文件路径
+    fn match_style_require_call(&self, expr: &Expr) -> Option<Str> {
+        match_require_call(expr).filter(|src| self.is_style_file(&src.value)).cloned()
+    }
+
+    fn is_class_name_helper_source(&self, src: &str) -> bool {
+        self.config.class_name_helpers.iter().any(|helper| {
+            src == helper || src.strip_prefix(helper.as_str()).is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+
+    // 收集 `import cx from 'classnames'` 与 `import { clsx } from 'clsx'` 的绑定
+    fn collect_class_name_helper_import(&mut self, import: &ImportDecl) {
+        if !self.is_class_name_helper_source(&import.src.value) {
+            return;
+        }
+        for specifier in &import.specifiers {
+            match specifier {
+                ImportSpecifier::Default(ImportDefaultSpecifier { local, .. })
+                | ImportSpecifier::Named(ImportNamedSpecifier { local, .. }) => {
+                    self.class_name_helpers.push(local.to_id())
                 }
+                ImportSpecifier::Namespace(_) => {}
             }
         }
-        false
     }
 
-    fn create_matcher_stmt(&mut self) -> Stmt {
-        let matcher_ident = Ident::new(
-            "_matcher".into(),
-            Span::default(),
-            SyntaxContext::default(),
-        );
-        self.matcher_ident = Some(matcher_ident.clone());
+    // 收集 `const cx = require('classnames')` 的绑定
+    fn collect_class_name_helper_require(&mut self, stmt: &Stmt) {
+        let Stmt::Decl(Decl::Var(var)) = stmt else {
+            return;
+        };
+        for decl in &var.decls {
+            let (Pat::Ident(binding), Some(init)) = (&decl.name, &decl.init) else {
+                continue;
+            };
+            if match_require_call(init).is_some_and(|src| self.is_class_name_helper_source(&src.value)) {
+                self.class_name_helpers.push(binding.id.to_id());
+            }
+        }
+    }
+
+    // 匹配顶层的样式 require 语句：`require('./a.css')` 或 `const s = require('./a.css')`
+    fn match_style_require(&self, stmt: &Stmt) -> Option<StyleRequire> {
+        match stmt {
+            Stmt::Expr(ExprStmt { expr, .. }) => self
+                .match_style_require_call(expr)
+                .map(|src| StyleRequire { src, binding: None }),
+            Stmt::Decl(Decl::Var(var)) if var.decls.len() == 1 => {
+                let decl = &var.decls[0];
+                let src = self.match_style_require_call(decl.init.as_deref()?)?;
+                let binding = match &decl.name {
+                    Pat::Ident(binding) => Some(binding.id.clone()),
+                    _ => None,
+                };
+                Some(StyleRequire { src, binding })
+            }
+            _ => None,
+        }
+    }
+
+    // 返回样式导入中用户已声明的默认/命名空间绑定，没有时按 import_style 补充一个 `style_N` 绑定。
+    // 命名空间导入不能与具名导入共存，此时返回需要插入在其后的导入语句
+    fn style_import_binding(
+        &self,
+        import: &mut ImportDecl,
+        index: usize,
+        import_style: ImportStyle,
+    ) -> (Ident, Option<ModuleItem>) {
+        let existing_style = import.specifiers.iter().find_map(|specifier| match specifier {
+            ImportSpecifier::Default(ImportDefaultSpecifier { local, .. })
+            | ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => Some(local.clone()),
+            ImportSpecifier::Named(_) => None,
+        });
+        if let Some(local) = existing_style {
+            return (local, None);
+        }
+
+        let default_style = private_ident!(format!("style_{}", index));
+        if import_style == ImportStyle::Default {
+            // 默认导入可以与具名导入写在同一条语句中
+            import.specifiers.insert(
+                0,
+                ImportSpecifier::Default(ImportDefaultSpecifier {
+                    span: import.span,
+                    local: default_style.clone(),
+                }),
+            );
+            return (default_style, None);
+        }
+        let specifier = ImportSpecifier::Namespace(ImportStarAsSpecifier {
+            span: import.span,
+            local: default_style.clone(),
+        });
+        if import.specifiers.is_empty() {
+            import.specifiers.push(specifier);
+            (default_style, None)
+        } else {
+            (default_style.clone(), Some(create_import_item(import, vec![specifier])))
+        }
+    }
+
+    // 为没有可用绑定的样式 require 生成 `const style_N = require(...)`：纯副作用语句原地替换，
+    // 解构等形式保留原语句，返回需要插入在其后的 require 语句
+    fn rewrite_style_require(&self, stmt: &mut Stmt, require: &StyleRequire, index: usize) -> (Ident, Option<Stmt>) {
+        if let Some(binding) = &require.binding {
+            return (binding.clone(), None);
+        }
+
+        let default_style = private_ident!(format!("style_{}", index));
+        let span = stmt.span();
+        let require_stmt = Stmt::Decl(Decl::Var(Box::new(VarDecl {
+            span,
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span,
+                name: Pat::Ident(BindingIdent {
+                    id: default_style.clone(),
+                    type_ann: None,
+                }),
+                init: Some(Box::new(create_require_call(require.src.clone()))),
+                definite: false,
+            }],
+            ctxt: SyntaxContext::default(),
+        })));
+        match stmt {
+            Stmt::Expr(_) => {
+                *stmt = require_stmt;
+                (default_style, None)
+            }
+            _ => (default_style, Some(require_stmt)),
+        }
+    }
+
+    fn prepare_runtime(&mut self, style_srcs: &[String], runtime_span: Span) {
+        self.runtime_span = runtime_span;
+        self.styles_ident = Some(private_ident!("_styles"));
+        self.matcher_ident = Some(private_ident!("_matcher"));
+        self.local_matcher_ident = None;
+        self.get_matcher_ident = Some(private_ident!("getMatcher"));
+        self.style_srcs = style_srcs.to_vec();
+        self.stylesheets = self.load_stylesheets(style_srcs);
+        self.local_idents = self.create_local_idents();
+        self.referenced_class_names.clear();
+        self.runtime_used = false;
+        self.matcher_used = false;
+        self.usage = (self.config.usage_manifest != UsageManifest::None).then(|| ClassUsage::new(style_srcs));
+    }
+
+    // 读取所有相对路径导入的样式文件，任一样式文件无法读取时返回 None
+    fn load_stylesheets(&self, style_srcs: &[String]) -> Option<Vec<Stylesheet>> {
+        // 多个样式文件时需要检查类名是否在多个文件中重复
+        if self.config.local_ident_name.is_none()
+            && self.missing_class_level() == MissingClass::Ignore
+            && self.config.import_style != ImportStyle::Named
+            && style_srcs.len() < 2
+        {
+            return None;
+        }
+        style_srcs.iter().map(|src| self.read_stylesheet(src)).collect()
+    }
+
+    // 读取相对路径导入的样式文件，非相对路径或缺少当前文件路径时返回 None
+    fn read_stylesheet(&self, src: &str) -> Option<Stylesheet> {
+        if !src.starts_with('.') {
+            return None;
+        }
+        let cwd = PathBuf::from(self.cwd.clone().unwrap_or_default());
+        let filename = cwd.join(self.filename.as_ref()?);
+        Stylesheet::read(&filename.parent()?.join(src))
+    }
+
+    // 按照 localIdentName 计算单个样式文件中类名的哈希类名
+    fn generate_local_ident(&self, stylesheet: &Stylesheet, class_name: &str) -> Option<String> {
+        let local_ident_name = self.config.local_ident_name.as_ref()?;
+        let cwd = PathBuf::from(self.cwd.clone().unwrap_or_default());
+        let context = match &self.config.local_ident_context {
+            Some(context) => cwd.join(context),
+            None => cwd,
+        };
+        Some(generate_local_ident(
+            local_ident_name,
+            &context,
+            &stylesheet.path,
+            class_name,
+            self.config.local_ident_hash_salt.as_deref(),
+        ))
+    }
+
+    // 按照 localIdentName 计算哈希类名，回退到运行时映射时返回 None
+    fn create_local_idents(&self) -> Option<HashMap<String, String>> {
+        self.config.local_ident_name.as_ref()?;
+
+        let mut local_idents = HashMap::new();
+        for stylesheet in self.stylesheets.as_ref()? {
+            for class_name in &stylesheet.class_names {
+                let local_ident = self.generate_local_ident(stylesheet, class_name)?;
+                // 与 Object.assign 的合并顺序一致，后导入的样式覆盖先导入的
+                local_idents.insert(class_name.clone(), local_ident);
+            }
+        }
+        Some(local_idents)
+    }
+
+    // 以样式导入的绑定名作为作用域名，`global` 与 `local` 保留给内置语法
+    fn collect_style_scope(&mut self, binding: &Ident, index: usize) {
+        if binding.sym == *"global" || binding.sym == *"local" {
+            return;
+        }
+        self.style_scopes.push(StyleScope {
+            name: binding.sym.to_string(),
+            index,
+            binding: binding.clone(),
+        });
+    }
+
+    fn scope_names(&self) -> Vec<&str> {
+        self.style_scopes.iter().map(|scope| scope.name.as_str()).collect()
+    }
+
+    // strict-local 模式下未单独配置 missingClass 时，缺失的类名视为错误
+    fn missing_class_level(&self) -> MissingClass {
+        match (self.config.missing_class, self.prefer) {
+            (MissingClass::Ignore, Prefer::StrictLocal) => MissingClass::Error,
+            (missing_class, _) => missing_class,
+        }
+    }
+
+    // 检查字面量中的局部类名是否存在于导入的样式文件中，`:name(...)` 中的类名只在对应的样式文件中查找
+    fn check_missing_class_names(&self, value: &str, span: Span, prefer: Prefer) {
+        let Some(stylesheets) = &self.stylesheets else {
+            return;
+        };
+        let missing_class = self.missing_class_level();
+        if missing_class == MissingClass::Ignore {
+            return;
+        }
+
+        let class_names = parse_class_names(value, prefer, &self.scope_names());
+        let locals = class_names
+            .locals
+            .into_iter()
+            .map(|class_name| (class_name, stylesheets.iter().collect::<Vec<_>>()));
+        let scoped = class_names.scoped.into_iter().map(|(scope, class_name)| {
+            let scope = self.style_scopes.iter().find(|style_scope| style_scope.name == scope).unwrap();
+            (class_name, vec![&stylesheets[scope.index]])
+        });
+        for (class_name, stylesheets) in locals.chain(scoped) {
+            if stylesheets.iter().any(|stylesheet| stylesheet.contains(&class_name)) {
+                continue;
+            }
+
+            let mut candidates: Vec<(usize, &String)> = stylesheets
+                .iter()
+                .flat_map(|stylesheet| &stylesheet.class_names)
+                .map(|name| (strsim::levenshtein(&class_name, name), name))
+                .filter(|(distance, _)| *distance <= 2.max(class_name.len() / 3))
+                .collect();
+            candidates.sort();
+            candidates.dedup_by(|a, b| a.1 == b.1);
+
+            let message = format!("class `{}` does not exist in the imported stylesheets", class_name);
+            HANDLER.with(|handler| {
+                let mut diagnostic = if missing_class == MissingClass::Error {
+                    handler.struct_span_err(span, &message)
+                } else {
+                    handler.struct_span_warn(span, &message)
+                };
+                if !candidates.is_empty() {
+                    let names: Vec<String> =
+                        candidates.iter().take(3).map(|(_, name)| format!("`{}`", name)).collect();
+                    diagnostic.help(&format!("did you mean {}?", names.join(", ")));
+                }
+                diagnostic.emit();
+            });
+        }
+    }
+
+    // 未限定作用域的局部类名同时存在于多个样式文件中时，合并后只有最后一个生效
+    fn check_ambiguous_class_names(&self, value: &str, span: Span, prefer: Prefer) {
+        let Some(stylesheets) = &self.stylesheets else {
+            return;
+        };
+
+        for class_name in parse_class_names(value, prefer, &self.scope_names()).locals {
+            let indexes: Vec<usize> = stylesheets
+                .iter()
+                .enumerate()
+                .filter(|(_, stylesheet)| stylesheet.contains(&class_name))
+                .map(|(index, _)| index)
+                .collect();
+            if indexes.len() < 2 {
+                continue;
+            }
+
+            let srcs: Vec<String> = indexes.iter().map(|index| format!("`{}`", self.style_srcs[*index])).collect();
+            let message = format!(
+                "class `{}` is defined in multiple imported stylesheets ({}), the last one wins",
+                class_name,
+                srcs.join(", ")
+            );
+            let scopes: Vec<String> = self
+                .style_scopes
+                .iter()
+                .filter(|scope| indexes.contains(&scope.index))
+                .map(|scope| format!("`:{}({})`", scope.name, class_name))
+                .collect();
+            HANDLER.with(|handler| {
+                let mut diagnostic = handler.struct_span_warn(span, &message);
+                if scopes.is_empty() {
+                    diagnostic.help("bind the style imports to names and use `:name(...)` to pick one");
+                } else {
+                    diagnostic.help(&format!("use {} to pick one", scopes.join(" or ")));
+                }
+                diagnostic.emit();
+            });
+        }
+    }
+
+    // 运行时所在的模块与导出名
+    fn helper_source(&self) -> (&str, &str) {
+        match &self.config.helper {
+            Helper::Custom { module, export } => (module, export),
+            _ => (HELPERS_MODULE, "getMatcher"),
+        }
+    }
+
+    // 添加 getMatcher 导入（inline 模式下为内联实现），生成的绑定均使用独立的 Mark，由 hygiene 处理与用户代码的命名冲突
+    fn create_get_matcher_item(&self) -> ModuleItem {
+        let get_matcher_ident = self.get_matcher_ident.clone().unwrap();
+        if self.config.helper == Helper::Inline {
+            return ModuleItem::Stmt(create_inline_get_matcher(get_matcher_ident));
+        }
+
+        let (module, export) = self.helper_source();
+        let imported = if export == "getMatcher" {
+            None
+        } else if Ident::verify_symbol(export).is_ok() || export == "default" {
+            Some(ModuleExportName::Ident(Ident::new(export.into(), self.runtime_span, SyntaxContext::default())))
+        } else {
+            Some(ModuleExportName::Str(export.into()))
+        };
+        ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+            span: self.runtime_span,
+            src: Box::new(Str {
+                span: self.runtime_span,
+                value: module.into(),
+                raw: Some(format!("'{}'", module).into()),
+            }),
+            type_only: false,
+            with: None,
+            phase: Default::default(),
+            specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
+                span: self.runtime_span,
+                local: get_matcher_ident,
+                imported,
+                is_type_only: false,
+            })],
+        }))
+    }
+
+    // 脚本中使用 `const getMatcher = require('...').getMatcher`
+    fn create_get_matcher_require(&self) -> Stmt {
+        let get_matcher_ident = self.get_matcher_ident.clone().unwrap();
+        if self.config.helper == Helper::Inline {
+            return create_inline_get_matcher(get_matcher_ident);
+        }
+
+        let (module, export) = self.helper_source();
+        let prop = if Ident::verify_symbol(export).is_ok() {
+            MemberProp::Ident(IdentName::new(export.into(), self.runtime_span))
+        } else {
+            MemberProp::Computed(ComputedPropName {
+                span: self.runtime_span,
+                expr: Box::new(Expr::Lit(Lit::Str(export.into()))),
+            })
+        };
+        Stmt::Decl(Decl::Var(Box::new(VarDecl {
+            span: self.runtime_span,
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: self.runtime_span,
+                name: Pat::Ident(BindingIdent {
+                    id: get_matcher_ident,
+                    type_ann: None,
+                }),
+                init: Some(Box::new(Expr::Member(MemberExpr {
+                    span: self.runtime_span,
+                    obj: Box::new(create_require_call(Str {
+                        span: self.runtime_span,
+                        value: module.into(),
+                        raw: Some(format!("'{}'", module).into()),
+                    })),
+                    prop,
+                }))),
+                definite: false,
+            }],
+            ctxt: SyntaxContext::default(),
+        })))
+    }
+
+    // 创建 _styles 对象
+    fn create_styles_stmt(&self, default_styles: &[Ident]) -> Stmt {
+        Stmt::Decl(Decl::Var(Box::new(VarDecl {
+            span: self.runtime_span,
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: self.runtime_span,
+                name: Pat::Ident(BindingIdent {
+                    id: self.styles_ident.clone().unwrap(),
+                    type_ann: None,
+                }),
+                init: Some(Box::new(Expr::Call(CallExpr {
+                    span: self.runtime_span,
+                    callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                        span: self.runtime_span,
+                        obj: Box::new(Expr::Ident(Ident::new(
+                            "Object".into(),
+                            self.runtime_span,
+                            SyntaxContext::default(),
+                        ))),
+                        prop: MemberProp::Ident(IdentName::new(
+                            "assign".into(),
+                            self.runtime_span,
+                        )),
+                    }))),
+                    args: std::iter::once(Expr::Object(ObjectLit {
+                        span: self.runtime_span,
+                        props: vec![],
+                    }))
+                    .chain(default_styles.iter().map(|style| Expr::Ident(style.clone())))
+                    .map(|expr| ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(expr),
+                    })
+                    .collect(),
+                    type_args: None,
+                    ctxt: SyntaxContext::default(),
+                }))),
+                definite: false,
+            }],
+            ctxt: SyntaxContext::default(),
+        })))
+    }
+
+    // 创建 `_matcher(expr)` 调用，标记当前文件需要运行时。_matcher 按文件的 prefer 创建，
+    // 文件的 prefer 为 global 而类名需要按局部处理（如 styleName）时改用 `_localMatcher`
+    fn create_matcher_call(&mut self, expr: Box<Expr>, span: Span, prefer: Prefer) -> Expr {
+        self.runtime_used = true;
+        let matcher_ident = if self.prefer == Prefer::Global && prefer != Prefer::Global {
+            self.local_matcher_ident.get_or_insert_with(|| private_ident!("_localMatcher")).clone()
+        } else {
+            self.matcher_used = true;
+            self.matcher_ident.clone().unwrap()
+        };
+        Expr::Call(CallExpr {
+            span,
+            callee: Callee::Expr(Box::new(Expr::Ident(matcher_ident))),
+            args: vec![ExprOrSpread { spread: None, expr }],
+            type_args: None,
+            ctxt: SyntaxContext::default(),
+        })
+    }
+
+    // 转换后的代码是否引用了 _styles（包括通过 _matcher），都没有时不注入任何运行时代码，
+    // 如只有 `className={undefined}`、全局类名或构建期已映射为哈希类名的文件
+    fn styles_used(&self) -> bool {
+        self.runtime_used || !self.referenced_class_names.is_empty()
+    }
+
+    // named 导入依赖静态解析收集引用到的类名
+    fn resolves_literals(&self) -> bool {
+        self.config.static_resolve || self.local_idents.is_some() || self.config.import_style == ImportStyle::Named
+    }
+
+    // 字面量类名在可能时于编译期解析，否则交给运行时的 _matcher
+    fn transform_class_name_literal(&mut self, str_lit: &Str, span: Span, site: ClassNameSite, prefer: Prefer) -> Expr {
+        self.record_class_usage(&str_lit.value, prefer);
+        self.check_missing_class_names(&str_lit.value, span, prefer);
+        self.check_ambiguous_class_names(&str_lit.value, span, prefer);
+        if site != ClassNameSite::Attribute || self.resolves_literals() {
+            return self.create_static_class_names_expr(&str_lit.value, str_lit.span, prefer);
+        }
+        let call_span = str_lit.span;
+        let str_lit = Str {
+            span: str_lit.span,
+            value: str_lit.value.clone(),
+            raw: None,
+        };
+        self.create_matcher_call(Box::new(Expr::Lit(Lit::Str(str_lit))), call_span, prefer)
+    }
+
+    fn record_class_usage(&mut self, value: &str, prefer: Prefer) {
+        if self.usage.is_none() {
+            return;
+        }
+        let class_names = parse_class_names(value, prefer, &self.scope_names());
+        let scoped: Vec<(String, String)> = class_names
+            .scoped
+            .into_iter()
+            .map(|(scope, class_name)| {
+                let scope = self.style_scopes.iter().find(|style_scope| style_scope.name == scope).unwrap();
+                (self.style_srcs[scope.index].clone(), class_name)
+            })
+            .collect();
+        let usage = self.usage.as_mut().unwrap();
+        usage.locals.extend(class_names.locals.into_iter().filter(|class_name| !class_name.is_empty()));
+        usage.globals.extend(class_names.globals.into_iter().filter(|class_name| !class_name.is_empty()));
+        for (src, class_name) in scoped {
+            usage.scoped.entry(src).or_default().insert(class_name);
+        }
+    }
+
+    fn is_class_name_helper_call(&self, call: &CallExpr) -> bool {
+        match &call.callee {
+            Callee::Expr(callee) => match &**callee {
+                Expr::Ident(ident) => self.class_name_helpers.contains(&ident.to_id()),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    // 分析动态类名表达式：明显不是字符串的值保持原样，条件/逻辑表达式将 _matcher 下推到各个分支。
+    // 位于属性值以外的位置时字面量总是在编译期解析；classnames/clsx 等调用的参数中对象的键与数组元素
+    // 也会逐个映射，框架预设中接受对象/数组的属性（如 Solid 的 classList）同样逐个映射
+    fn transform_class_name_expr(&mut self, mut expr: Box<Expr>, site: ClassNameSite, prefer: Prefer) -> Box<Expr> {
+        let in_helper = site == ClassNameSite::Helper;
+        let in_attribute = site == ClassNameSite::Attribute;
+        let resolve_literal = !in_attribute || self.resolves_literals();
+        match &mut *expr {
+            Expr::Lit(Lit::Str(str_lit)) => {
+                let str_lit = str_lit.clone();
+                Box::new(self.transform_class_name_literal(&str_lit, str_lit.span, site, prefer))
+            }
+            // 不含插值的模板字符串在可静态解析时按字面量处理，否则原样交给 _matcher
+            Expr::Tpl(tpl) if tpl.exprs.is_empty() && resolve_literal => {
+                let str_lit = Str {
+                    span: tpl.span,
+                    value: tpl.quasis[0].cooked.clone().unwrap_or_else(|| tpl.quasis[0].raw.clone()),
+                    raw: None,
+                };
+                Box::new(self.transform_class_name_literal(&str_lit, str_lit.span, site, prefer))
+            }
+            Expr::Call(call) if self.is_class_name_helper_call(call) => {
+                for arg in &mut call.args {
+                    if arg.spread.is_none() {
+                        arg.expr = self.transform_class_name_expr(arg.expr.take(), ClassNameSite::Helper, prefer);
+                    }
+                }
+                expr
+            }
+            Expr::Object(object) if in_helper || in_attribute && self.class_attribute.objects => {
+                for prop in &mut object.props {
+                    if let PropOrSpread::Prop(prop) = prop {
+                        self.transform_class_name_prop(prop, prefer);
+                    }
+                }
+                expr
+            }
+            Expr::Array(array) if in_helper || in_attribute && self.class_attribute.arrays => {
+                for elem in array.elems.iter_mut().flatten() {
+                    if elem.spread.is_none() {
+                        elem.expr = self.transform_class_name_expr(elem.expr.take(), ClassNameSite::Helper, prefer);
+                    }
+                }
+                expr
+            }
+            Expr::Lit(_)
+            | Expr::Object(_)
+            | Expr::Array(_)
+            | Expr::JSXElement(_)
+            | Expr::JSXFragment(_)
+            | Expr::Arrow(_)
+            | Expr::Fn(_)
+            | Expr::Class(_)
+            | Expr::Unary(UnaryExpr { op: UnaryOp::Void, .. }) => expr,
+            Expr::Ident(ident) if ident.sym == *"undefined" => expr,
+            Expr::Paren(paren) => {
+                paren.expr = self.transform_class_name_expr(paren.expr.take(), site, prefer);
+                expr
+            }
+            Expr::Cond(cond) => {
+                cond.cons = self.transform_class_name_expr(cond.cons.take(), site, prefer);
+                cond.alt = self.transform_class_name_expr(cond.alt.take(), site, prefer);
+                expr
+            }
+            Expr::Bin(bin) if bin.op == BinaryOp::LogicalAnd => {
+                bin.right = self.transform_class_name_expr(bin.right.take(), site, prefer);
+                expr
+            }
+            Expr::Bin(bin) if matches!(bin.op, BinaryOp::LogicalOr | BinaryOp::NullishCoalescing) => {
+                bin.left = self.transform_class_name_expr(bin.left.take(), site, prefer);
+                bin.right = self.transform_class_name_expr(bin.right.take(), site, prefer);
+                expr
+            }
+            Expr::Seq(seq) => {
+                if let Some(last) = seq.exprs.pop() {
+                    seq.exprs.push(self.transform_class_name_expr(last, site, prefer));
+                }
+                expr
+            }
+            // classnames/clsx 的参数可以是对象或数组变量，交给 _matcher 会被当作非字符串丢弃，
+            // 因此标识符、成员访问与函数调用等保持原样，只有模板字符串与字符串拼接才交给 _matcher
+            Expr::Tpl(_) | Expr::Bin(BinExpr { op: BinaryOp::Add, .. }) => {
+                let span = expr.span();
+                Box::new(self.create_matcher_call(expr, span, prefer))
+            }
+            _ if in_helper => expr,
+            _ => {
+                let span = expr.span();
+                Box::new(self.create_matcher_call(expr, span, prefer))
+            }
+        }
+    }
+
+    // 将 classnames 对象参数的键映射为最终类名，如 `{ active: isActive }` -> `{ [_styles.active || "active"]: isActive }`
+    fn transform_class_name_prop(&mut self, prop: &mut Prop, prefer: Prefer) {
+        if let Prop::Shorthand(ident) = prop {
+            *prop = Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(IdentName::new(ident.sym.clone(), ident.span)),
+                value: Box::new(Expr::Ident(ident.clone())),
+            });
+        }
+        let Prop::KeyValue(KeyValueProp { key, .. }) = prop else {
+            return;
+        };
+        *key = match key.take() {
+            PropName::Ident(IdentName { span, sym }) => {
+                self.transform_class_name_key(&Str { span, value: sym, raw: None }, prefer)
+            }
+            PropName::Str(str_lit) => self.transform_class_name_key(&str_lit, prefer),
+            PropName::Computed(computed) => PropName::Computed(ComputedPropName {
+                span: computed.span,
+                expr: self.transform_class_name_expr(computed.expr, ClassNameSite::String, prefer),
+            }),
+            key => key,
+        };
+    }
+
+    fn transform_class_name_key(&mut self, str_lit: &Str, prefer: Prefer) -> PropName {
+        match self.transform_class_name_literal(str_lit, str_lit.span, ClassNameSite::String, prefer) {
+            Expr::Lit(Lit::Str(str_lit)) => PropName::Str(str_lit),
+            expr => PropName::Computed(ComputedPropName {
+                span: str_lit.span,
+                expr: Box::new(expr),
+            }),
+        }
+    }
+
+    fn create_matcher_stmt(&self, matcher_ident: Ident, prefer: Prefer) -> Stmt {
 
         Stmt::Decl(Decl::Var(Box::new(VarDecl {
-            span: Span::default(),
+            span: self.runtime_span,
             kind: VarDeclKind::Const,
             declare: false,
             decls: vec![VarDeclarator {
-                span: Span::default(),
+                span: self.runtime_span,
                 name: Pat::Ident(BindingIdent {
                     id: matcher_ident,
                     type_ann: None,
                 }),
                 init: Some(Box::new(Expr::Call(CallExpr {
-                    span: Span::default(),
-                    callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                        "getMatcher".into(),
-                        Span::default(),
-                        SyntaxContext::default(),
-                    )))),
+                    span: self.runtime_span,
+                    callee: Callee::Expr(Box::new(Expr::Ident(self.get_matcher_ident.clone().unwrap()))),
                     args: vec![
                         ExprOrSpread {
                             spread: None,
@@ -65,12 +1144,15 @@ impl JsxCssModulesVisitor {
                         ExprOrSpread {
                             spread: None,
                             expr: Box::new(Expr::Lit(Lit::Str(Str {
-                                span: Span::default(),
-                                value: self.config.prefer.clone().into(),
-                                raw: Some(format!("'{}'", self.config.prefer).into()),
+                                span: self.runtime_span,
+                                value: prefer.as_str().into(),
+                                raw: Some(format!("'{}'", prefer.as_str()).into()),
                             }))),
                         },
-                    ],
+                    ]
+                    .into_iter()
+                    .chain(self.create_scopes_arg())
+                    .collect(),
                     type_args: None,
                     ctxt: SyntaxContext::default(),
                 }))),
@@ -79,210 +1161,640 @@ impl JsxCssModulesVisitor {
             ctxt: SyntaxContext::default(),
         })))
     }
-}
 
-impl Fold for JsxCssModulesVisitor {
-    fn fold_module(&mut self, mut module: Module) -> Module {
-        // 收集样式导入
-        let mut style_imports = Vec::new();
-        let mut style_import_indices = Vec::new();
-        let mut all_imports = Vec::new();
-        for (i, item) in module.body.iter().enumerate() {
-            if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item {
-                all_imports.push((i, import.clone()));
-                if self.is_style_import(import) {
-                    style_imports.push(import.clone());
-                    style_import_indices.push(i);
+    // 声明用到的 _matcher 与 _localMatcher
+    fn create_matcher_stmts(&self) -> Vec<Stmt> {
+        let matcher = self.matcher_used.then(|| (self.matcher_ident.clone().unwrap(), self.prefer));
+        let local_matcher = self.local_matcher_ident.clone().map(|ident| (ident, Prefer::Local));
+        matcher
+            .into_iter()
+            .chain(local_matcher)
+            .map(|(matcher_ident, prefer)| self.create_matcher_stmt(matcher_ident, prefer))
+            .collect()
+    }
+
+    // getMatcher 的第三个参数：`{ btn, card }`，用于运行时解析 `:name(...)`
+    fn create_scopes_arg(&self) -> Option<ExprOrSpread> {
+        if self.style_scopes.is_empty() {
+            return None;
+        }
+        Some(ExprOrSpread {
+            spread: None,
+            expr: Box::new(Expr::Object(ObjectLit {
+                span: self.runtime_span,
+                props: self
+                    .style_scopes
+                    .iter()
+                    .map(|scope| PropOrSpread::Prop(Box::new(Prop::Shorthand(scope.binding.clone()))))
+                    .collect(),
+            })),
+        })
+    }
+
+    // 创建 `_styles.xxx || "xxx"` 表达式
+    fn create_style_lookup_expr(&mut self, class_name: &str, span: Span) -> Expr {
+        if !self.referenced_class_names.iter().any(|name| name == class_name) {
+            self.referenced_class_names.push(class_name.to_string());
+        }
+        create_lookup_expr(self.styles_ident.clone().unwrap(), class_name, span)
+    }
+
+    // 在编译期解析字面量类名，输出结果与 getMatcher 运行时一致，生成的节点沿用原字面量的位置
+    fn create_static_class_names_expr(&mut self, value: &str, span: Span, prefer: Prefer) -> Expr {
+        let class_names = parse_class_names(value, prefer, &self.scope_names());
+        let mut parts = vec![];
+        for class_name in &class_names.locals {
+            parts.push(match &self.local_idents {
+                Some(local_idents) => ClassNamePart::Static(local_idents.get(class_name).unwrap_or(class_name).clone()),
+                None if !class_name.is_empty() => ClassNamePart::Lookup(self.create_style_lookup_expr(class_name, span)),
+                None => ClassNamePart::Static(class_name.clone()),
+            });
+        }
+        for (scope, class_name) in &class_names.scoped {
+            let scope = self.style_scopes.iter().find(|style_scope| &style_scope.name == scope).unwrap();
+            parts.push(match (&self.local_idents, &self.stylesheets) {
+                (Some(_), Some(stylesheets)) => {
+                    let stylesheet = &stylesheets[scope.index];
+                    let local_ident = stylesheet
+                        .contains(class_name)
+                        .then(|| self.generate_local_ident(stylesheet, class_name))
+                        .flatten();
+                    ClassNamePart::Static(local_ident.unwrap_or(class_name.clone()))
+                }
+                _ => ClassNamePart::Lookup(create_lookup_expr(scope.binding.clone(), class_name, span)),
+            });
+        }
+        parts.extend(class_names.globals.into_iter().map(ClassNamePart::Static));
+
+        let mut quasis = vec![String::new()];
+        let mut exprs = vec![];
+        for (i, part) in parts.into_iter().enumerate() {
+            if i > 0 {
+                quasis.last_mut().unwrap().push(' ');
+            }
+            match part {
+                ClassNamePart::Static(class_name) => quasis.last_mut().unwrap().push_str(&class_name),
+                ClassNamePart::Lookup(expr) => {
+                    exprs.push(expr);
+                    quasis.push(String::new());
                 }
             }
         }
 
-        if !style_imports.is_empty() {
-            // 确保每个样式导入都有默认导入
-            let mut default_styles = Vec::new();
-            let mut style_imports_map = std::collections::HashMap::new();
-            for (i, import) in style_imports.iter_mut().enumerate() {
-                let default_style = Ident::new(
-                    format!("style_{}", i).into(),
-                    Span::default(),
-                    SyntaxContext::default(),
-                );
-                import.specifiers = vec![if self.config.import_style == "namespace" {
-                    ImportSpecifier::Namespace(ImportStarAsSpecifier {
-                        span: Span::default(),
-                        local: default_style.clone(),
-                    })
-                } else {
-                    ImportSpecifier::Default(ImportDefaultSpecifier {
-                        span: Span::default(),
-                        local: default_style.clone(),
-                    })
-                }];
-                default_styles.push(default_style);
-                style_imports_map.insert(import.src.value.to_string(), import.clone());
+        // 映射后的局部类名不会以空白开头或结尾，因此只需裁剪首尾的静态片段
+        let first = quasis.first().unwrap().trim_start().to_string();
+        *quasis.first_mut().unwrap() = first;
+        let last = quasis.last().unwrap().trim_end().to_string();
+        *quasis.last_mut().unwrap() = last;
+
+        if exprs.is_empty() {
+            return Expr::Lit(Lit::Str(Str {
+                span,
+                value: quasis[0].as_str().into(),
+                raw: None,
+            }));
+        }
+        if exprs.len() == 1 && quasis.iter().all(|quasi| quasi.is_empty()) {
+            return exprs.pop().unwrap();
+        }
+
+        create_tpl(quasis, exprs, span)
+    }
+
+    // 为每个样式导入补充 `import { a as _a } from` 形式的具名导入，并用这些绑定声明 _styles。
+    // 只有读取到类名列表的样式文件才按名称导入其中存在的类名，无法读取的样式文件回退为命名空间导入，
+    // 避免导入不存在的导出
+    fn apply_named_imports(&self, module: &mut Module, style_imports: &[(usize, usize)]) {
+        if self.referenced_class_names.is_empty() {
+            return;
+        }
+
+        let read_stylesheets: Vec<Option<Stylesheet>>;
+        let stylesheets: Vec<Option<&Stylesheet>> = match &self.stylesheets {
+            Some(stylesheets) => stylesheets.iter().map(Some).collect(),
+            None => {
+                read_stylesheets = self.style_srcs.iter().map(|src| self.read_stylesheet(src)).collect();
+                read_stylesheets.iter().map(Option::as_ref).collect()
             }
+        };
 
-            // 添加 getMatcher 导入
-            let get_matcher_import = ImportDecl {
-                span: Span::default(),
-                src: Box::new(Str {
-                    span: Span::default(),
-                    value: "swc-plugin-jsx-css-modules/helpers".into(),
-                    raw: Some("'swc-plugin-jsx-css-modules/helpers'".into()),
-                }),
-                type_only: false,
-                with: None,
-                phase: Default::default(),
-                specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
-                    span: Span::default(),
-                    local: Ident::new(
-                        "getMatcher".into(),
-                        Span::default(),
-                        SyntaxContext::default(),
-                    ),
-                    imported: None,
-                    is_type_only: false,
-                })],
+        let mut class_names = self.referenced_class_names.clone();
+        class_names.sort();
+        let mut bindings: Vec<Vec<Expr>> = vec![vec![]; class_names.len()];
+        let mut insertions = vec![];
+        for &(i, body_index) in style_imports {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = &mut module.body[body_index] else {
+                continue;
             };
-
-            // 创建 _styles 对象
-            let styles_ident = Ident::new(
-                "_styles".into(),
-                Span::default(),
-                SyntaxContext::default(),
-            );
-            self.styles_ident = Some(styles_ident.clone());
-            let styles_assign = VarDecl {
-                span: Span::default(),
-                kind: VarDeclKind::Const,
-                declare: false,
-                decls: vec![VarDeclarator {
-                    span: Span::default(),
-                    name: Pat::Ident(BindingIdent {
-                        id: styles_ident,
-                        type_ann: None,
-                    }),
-                    init: Some(Box::new(Expr::Call(CallExpr {
-                        span: Span::default(),
-                        callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
-                            span: Span::default(),
-                            obj: Box::new(Expr::Ident(Ident::new(
-                                "Object".into(),
-                                Span::default(),
-                                SyntaxContext::default(),
-                            ))),
-                            prop: MemberProp::Ident(IdentName::new(
-                                "assign".into(),
-                                Span::default(),
-                            )),
-                        }))),
-                        args: std::iter::once(Expr::Object(ObjectLit {
-                            span: Span::default(),
-                            props: vec![],
-                        }))
-                        .chain(default_styles.iter().map(|style| Expr::Ident(style.clone())))
-                        .map(|expr| ExprOrSpread {
-                            spread: None,
-                            expr: Box::new(expr),
+            let Some(stylesheet) = stylesheets[i] else {
+                let (namespace, import_item) = self.style_import_binding(import, i, ImportStyle::Namespace);
+                if let Some(import_item) = import_item {
+                    insertions.push((body_index + 1, import_item));
+                }
+                for (class_name, class_bindings) in class_names.iter().zip(bindings.iter_mut()) {
+                    let prop = if Ident::verify_symbol(class_name).is_ok() {
+                        MemberProp::Ident(IdentName::new(class_name.as_str().into(), import.span))
+                    } else {
+                        MemberProp::Computed(ComputedPropName {
+                            span: import.span,
+                            expr: Box::new(Expr::Lit(Lit::Str(class_name.as_str().into()))),
                         })
-                        .collect(),
-                        type_args: None,
-                        ctxt: SyntaxContext::default(),
-                    }))),
-                    definite: false,
-                }],
-                ctxt: SyntaxContext::default(),
+                    };
+                    class_bindings.push(Expr::Member(MemberExpr {
+                        span: import.span,
+                        obj: Box::new(Expr::Ident(namespace.clone())),
+                        prop,
+                    }));
+                }
+                continue;
             };
 
-            // 重新组织导入语句
-            let mut new_body = Vec::new();
-            let mut current_index = 0;
+            let mut specifiers = vec![];
+            for (class_name, class_bindings) in class_names.iter().zip(bindings.iter_mut()) {
+                if !stylesheet.contains(class_name) {
+                    continue;
+                }
+                let local_name: String = class_name
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() || c == '$' { c } else { '_' })
+                    .collect();
+                let local = private_ident!(format!("_{}", local_name));
+                let imported = if Ident::verify_symbol(class_name).is_ok() {
+                    ModuleExportName::Ident(Ident::new(class_name.as_str().into(), import.span, SyntaxContext::default()))
+                } else {
+                    ModuleExportName::Str(class_name.as_str().into())
+                };
+                class_bindings.push(Expr::Ident(local.clone()));
+                specifiers.push(ImportSpecifier::Named(ImportNamedSpecifier {
+                    span: import.span,
+                    local,
+                    imported: Some(imported),
+                    is_type_only: false,
+                }));
+            }
+
+            let has_namespace = import
+                .specifiers
+                .iter()
+                .any(|specifier| matches!(specifier, ImportSpecifier::Namespace(_)));
+            if specifiers.is_empty() {
+                continue;
+            } else if has_namespace {
+                // 命名空间导入不能与具名导入共存，单独补一条导入语句
+                insertions.push((body_index + 1, create_import_item(import, specifiers)));
+            } else {
+                import.specifiers.extend(specifiers);
+            }
+        }
 
-            // 1. 按原始顺序添加导入
-            for (_, import) in all_imports {
-                if let Some(updated_import) = style_imports_map.get(&import.src.value.to_string()) {
-                    new_body.push(ModuleItem::ModuleDecl(ModuleDecl::Import(updated_import.clone())));
+        // 与 Object.assign 的合并顺序一致，后导入的样式优先
+        let props = class_names
+            .iter()
+            .zip(bindings)
+            .filter(|(_, class_bindings)| !class_bindings.is_empty())
+            .map(|(class_name, class_bindings)| {
+                let key = if Ident::verify_symbol(class_name).is_ok() {
+                    PropName::Ident(IdentName::new(class_name.as_str().into(), self.runtime_span))
                 } else {
-                    new_body.push(ModuleItem::ModuleDecl(ModuleDecl::Import(import)));
+                    PropName::Str(class_name.as_str().into())
+                };
+                let value = class_bindings
+                    .into_iter()
+                    .map(Box::new)
+                    .reduce(|right, left| {
+                        Box::new(Expr::Bin(BinExpr {
+                            span: self.runtime_span,
+                            op: BinaryOp::LogicalOr,
+                            left,
+                            right,
+                        }))
+                    })
+                    .unwrap();
+                PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp { key, value })))
+            })
+            .collect();
+        let styles_stmt = Stmt::Decl(Decl::Var(Box::new(VarDecl {
+            span: self.runtime_span,
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: self.runtime_span,
+                name: Pat::Ident(BindingIdent {
+                    id: self.styles_ident.clone().unwrap(),
+                    type_ann: None,
+                }),
+                init: Some(Box::new(Expr::Object(ObjectLit {
+                    span: self.runtime_span,
+                    props,
+                }))),
+                definite: false,
+            }],
+            ctxt: SyntaxContext::default(),
+        })));
+
+        if let Some(last_import_index) = module
+            .body
+            .iter()
+            .rposition(|item| matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_))))
+        {
+            insertions.push((last_import_index + 1, ModuleItem::Stmt(styles_stmt)));
+        }
+        insert_items(&mut module.body, insertions);
+    }
+
+    fn transform_module(&mut self, module: &mut Module) {
+        // 收集样式导入，只记录位置，之后原地修改
+        let mut style_srcs = Vec::new();
+        let mut style_imports = Vec::new();
+        let mut style_requires = Vec::new();
+        let mut runtime_span = Span::default();
+        self.class_name_helpers.clear();
+        self.style_scopes.clear();
+        for (i, item) in module.body.iter().enumerate() {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                    self.collect_class_name_helper_import(import);
+                    if self.is_style_import(import) {
+                        for specifier in &import.specifiers {
+                            if let ImportSpecifier::Default(ImportDefaultSpecifier { local, .. })
+                            | ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) = specifier
+                            {
+                                self.collect_style_scope(local, style_srcs.len());
+                            }
+                        }
+                        if style_srcs.is_empty() {
+                            runtime_span = import.span;
+                        }
+                        style_imports.push((style_srcs.len(), i));
+                        style_srcs.push(import.src.value.to_string());
+                    }
                 }
-                current_index += 1;
+                ModuleItem::Stmt(stmt) => {
+                    self.collect_class_name_helper_require(stmt);
+                    if let Some(require) = self.match_style_require(stmt) {
+                        if let Some(binding) = &require.binding {
+                            self.collect_style_scope(binding, style_srcs.len());
+                        }
+                        if style_srcs.is_empty() {
+                            runtime_span = stmt.span();
+                        }
+                        style_srcs.push(require.src.value.to_string());
+                        style_requires.push((style_srcs.len() - 1, i, require));
+                    }
+                }
+                _ => {}
             }
+        }
+
+        if style_srcs.is_empty() {
+            // 递归处理子节点
+            module.visit_mut_children_with(self);
+            return;
+        }
 
-            // 2. 添加 getMatcher 导入
-            new_body.push(ModuleItem::ModuleDecl(ModuleDecl::Import(get_matcher_import)));
+        self.prepare_runtime(&style_srcs, runtime_span);
 
-            // 3. 添加 styles 和 matcher 语句
-            new_body.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(styles_assign)))));
-            new_body.push(ModuleItem::Stmt(self.create_matcher_stmt()));
+        // 先处理 JSX，再根据是否仍需要运行时决定注入的内容；转换结果没有引用 _styles 时保持样式导入原样
+        module.visit_mut_children_with(self);
+        if !self.styles_used() {
+            return;
+        }
+        // 没有动态类名时按名称导入引用到的类名，否则回退到命名空间导入
+        if self.config.import_style == ImportStyle::Named && !self.runtime_used && style_requires.is_empty() {
+            self.apply_named_imports(module, &style_imports);
+            return;
+        }
+
+        // 确保每个样式导入都有可引用的绑定，优先复用用户已声明的默认/命名空间导入
+        let mut default_styles = vec![None; style_srcs.len()];
+        let mut insertions = vec![];
+        for (i, body_index) in style_imports {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = &mut module.body[body_index] else {
+                continue;
+            };
+            let import_style = match self.config.import_style {
+                ImportStyle::Default => ImportStyle::Default,
+                _ => ImportStyle::Namespace,
+            };
+            let (default_style, import_item) = self.style_import_binding(import, i, import_style);
+            default_styles[i] = Some(default_style);
+            if let Some(import_item) = import_item {
+                insertions.push((body_index + 1, import_item));
+            }
+        }
 
-            // 4. 添加剩余的内容
-            for item in module.body.iter().skip(current_index) {
-                if !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_))) {
-                    new_body.push(item.clone());
+        // 处理 require 形式的样式引用，_styles 需要声明在最后一个样式 require 之后
+        let last_require_index = style_requires.last().map(|(_, body_index, _)| *body_index);
+        for (i, body_index, require) in style_requires {
+            if let ModuleItem::Stmt(stmt) = &mut module.body[body_index] {
+                let (default_style, require_stmt) = self.rewrite_style_require(stmt, &require, i);
+                default_styles[i] = Some(default_style);
+                if let Some(require_stmt) = require_stmt {
+                    insertions.push((body_index + 1, ModuleItem::Stmt(require_stmt)));
                 }
             }
+        }
+        let default_styles: Vec<Ident> = default_styles.into_iter().flatten().collect();
 
-            module.body = new_body;
-        }
-
-        // 递归处理子节点
-        module.fold_children_with(self)
-    }
-
-    fn fold_jsx_element(&mut self, mut jsx: JSXElement) -> JSXElement {
-        if let Some(matcher_ident) = &self.matcher_ident {
-            for attr in &mut jsx.opening.attrs {
-                if let JSXAttrOrSpread::JSXAttr(attr) = attr {
-                    if let JSXAttrName::Ident(ident) = &attr.name {
-                        if ident.sym == *"className" {
-                            match &attr.value {
-                                // 处理字符串字面量
-                                Some(JSXAttrValue::Lit(Lit::Str(str_lit))) => {
-                                    attr.value = Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
-                                        span: Span::default(),
-                                        expr: JSXExpr::Expr(Box::new(Expr::Call(CallExpr {
-                                            span: Span::default(),
-                                            callee: Callee::Expr(Box::new(Expr::Ident(matcher_ident.clone()))),
-                                            args: vec![ExprOrSpread {
-                                                spread: None,
-                                                expr: Box::new(Expr::Lit(Lit::Str(Str {
-                                                    span: Span::default(),
-                                                    value: str_lit.value.clone().into(),
-                                                    raw: None
-                                                })))
-                                            }],
-                                            type_args: None,
-                                            ctxt: SyntaxContext::default()
-                                        })))
-                                    }));
-                                },
-                                // 处理 JSX 表达式容器（模板字符串和函数调用）
-                                Some(JSXAttrValue::JSXExprContainer(container)) => {
-                                    if let JSXExpr::Expr(expr) = &container.expr {
-                                        attr.value = Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
-                                            span: Span::default(),
-                                            expr: JSXExpr::Expr(Box::new(Expr::Call(CallExpr {
-                                                span: Span::default(),
-                                                callee: Callee::Expr(Box::new(Expr::Ident(matcher_ident.clone()))),
-                                                args: vec![ExprOrSpread {
-                                                    spread: None,
-                                                    expr: expr.clone()
-                                                }],
-                                                type_args: None,
-                                                ctxt: SyntaxContext::default()
-                                            })))
-                                        }));
-                                    }
-                                },
-                                _ => {}
-                            }
-                        }
-                    }
+        // 保持原有语句顺序：ESM 导入的绑定会被提升，getMatcher 导入与 _styles、_matcher 声明放在指令序言与开头的
+        // 导入之后，避免导入之间的顶层代码访问 _matcher 时处于暂时性死区；样式 require 的绑定不会提升，
+        // 存在样式 require 时 _styles 与 _matcher 声明需要放在最后一个样式 require 之后。没有 _matcher 调用时只声明 _styles
+        let directives_len = module
+            .body
+            .iter()
+            .take_while(|item| match item {
+                ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => matches!(&**expr, Expr::Lit(Lit::Str(_))),
+                _ => false,
+            })
+            .count();
+        let leading_imports_len = module.body[directives_len..]
+            .iter()
+            .take_while(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(_) | ModuleDecl::ExportAll(_)) => true,
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => export.src.is_some(),
+                _ => false,
+            })
+            .count();
+        let helper_position = directives_len + leading_imports_len;
+        let runtime_position = last_require_index.map_or(helper_position, |i| helper_position.max(i + 1));
+        if self.runtime_used {
+            insertions.push((helper_position, self.create_get_matcher_item()));
+        }
+        insertions.push((runtime_position, ModuleItem::Stmt(self.create_styles_stmt(&default_styles))));
+        for matcher_stmt in self.create_matcher_stmts() {
+            insertions.push((runtime_position, ModuleItem::Stmt(matcher_stmt)));
+        }
+        insert_items(&mut module.body, insertions);
+    }
+
+    fn transform_script(&mut self, script: &mut Script) {
+        // 收集样式 require
+        let mut style_srcs = Vec::new();
+        let mut style_requires = Vec::new();
+        let mut runtime_span = Span::default();
+        self.class_name_helpers.clear();
+        self.style_scopes.clear();
+        for (i, stmt) in script.body.iter().enumerate() {
+            self.collect_class_name_helper_require(stmt);
+            if let Some(require) = self.match_style_require(stmt) {
+                if let Some(binding) = &require.binding {
+                    self.collect_style_scope(binding, style_srcs.len());
+                }
+                if style_srcs.is_empty() {
+                    runtime_span = stmt.span();
                 }
+                style_srcs.push(require.src.value.to_string());
+                style_requires.push((style_srcs.len() - 1, i, require));
             }
         }
 
-        jsx.children = jsx.children.fold_with(self);
-        jsx
+        if style_srcs.is_empty() {
+            script.visit_mut_children_with(self);
+            return;
+        }
+
+        self.prepare_runtime(&style_srcs, runtime_span);
+        script.visit_mut_children_with(self);
+        if !self.styles_used() {
+            return;
+        }
+
+        let mut default_styles = vec![];
+        let mut insertions = vec![];
+        let last_require_index = style_requires.last().map(|(_, body_index, _)| *body_index);
+        for (i, body_index, require) in style_requires {
+            let (default_style, require_stmt) = self.rewrite_style_require(&mut script.body[body_index], &require, i);
+            default_styles.push(default_style);
+            if let Some(require_stmt) = require_stmt {
+                insertions.push((body_index + 1, require_stmt));
+            }
+        }
+
+        // 在最后一个样式 require 之后引入 getMatcher 并声明 _styles 与 _matcher，没有 _matcher 调用时只声明 _styles
+        if let Some(last_require_index) = last_require_index {
+            let runtime_position = last_require_index + 1;
+            if self.runtime_used {
+                insertions.push((runtime_position, self.create_get_matcher_require()));
+            }
+            insertions.push((runtime_position, self.create_styles_stmt(&default_styles)));
+            for matcher_stmt in self.create_matcher_stmts() {
+                insertions.push((runtime_position, matcher_stmt));
+            }
+        }
+        insert_items(&mut script.body, insertions);
+    }
+
+    // styleName 模式：将 `styleName` 按 CSS Modules 映射后合并到 `className`，并移除 `styleName`
+    fn transform_style_name(&mut self, attrs: &mut Vec<JSXAttrOrSpread>) {
+        let Some(index) = attrs.iter().position(|attr| is_attr_named(attr, STYLE_NAME)) else {
+            return;
+        };
+        let JSXAttrOrSpread::JSXAttr(style_name) = attrs.remove(index) else {
+            unreachable!();
+        };
+
+        // styleName 中的类名总是局部类名，字面量在编译期解析，不受 prefer: global 影响
+        let prefer = match self.prefer {
+            Prefer::Global => Prefer::Local,
+            prefer => prefer,
+        };
+        // 与 babel-plugin-react-css-modules 一致，styleName 只接受字符串，数组与对象拼接后会得到 `a,b`
+        // 或 `[object Object]`，报错并保持原样
+        if let Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        })) = &style_name.value
+        {
+            if matches!(expr.unwrap_parens(), Expr::Array(_) | Expr::Object(_)) {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(expr.span(), "`styleName` only accepts strings")
+                        .help("use `className` with classnames or clsx for arrays and objects")
+                        .emit();
+                });
+                attrs.insert(index, JSXAttrOrSpread::JSXAttr(style_name));
+                return;
+            }
+        }
+
+        let static_style_name = matches!(style_name.value, Some(JSXAttrValue::Lit(_)));
+        let style_expr = match style_name.value {
+            Some(JSXAttrValue::Lit(Lit::Str(str_lit))) => {
+                Some(self.transform_class_name_literal(&str_lit, style_name.span, ClassNameSite::String, prefer))
+            }
+            Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::Expr(expr),
+                ..
+            })) => Some(*self.transform_class_name_expr(expr, ClassNameSite::String, prefer)),
+            _ => None,
+        };
+        let Some(style_expr) = style_expr else {
+            return;
+        };
+
+        let span = style_name.span;
+        match attrs.iter_mut().find(|attr| is_attr_named(attr, "className")) {
+            Some(JSXAttrOrSpread::JSXAttr(class_name)) => {
+                let value = merge_style_name(class_name.value.take(), style_expr, static_style_name, span);
+                class_name.value = Some(create_attr_value(value, span));
+            }
+            _ => attrs.insert(
+                index,
+                JSXAttrOrSpread::JSXAttr(JSXAttr {
+                    span,
+                    name: JSXAttrName::Ident(IdentName::new("className".into(), span)),
+                    value: Some(create_attr_value(style_expr, span)),
+                }),
+            ),
+        }
+    }
+
+    fn leading_comments(&self, pos: BytePos) -> Vec<Comment> {
+        self.comments
+            .as_ref()
+            .and_then(|comments| comments.get_leading(pos))
+            .unwrap_or_default()
+    }
+
+    fn trailing_comments(&self, pos: BytePos) -> Vec<Comment> {
+        self.comments
+            .as_ref()
+            .and_then(|comments| comments.get_trailing(pos))
+            .unwrap_or_default()
+    }
+
+    fn add_usage_comment(&self, span: Span, usage: &ClassUsage) {
+        if let Some(comments) = &self.comments {
+            comments.add_leading(
+                span.lo,
+                Comment {
+                    kind: CommentKind::Block,
+                    span: Span::default(),
+                    text: usage.to_comment_text().into(),
+                },
+            );
+        }
+    }
+
+    // 读取文件开头注释中的指令，返回 None 表示整个文件跳过转换，否则返回当前文件生效的 prefer
+    fn file_prefer(&self, span: Span, first_item: Option<Span>) -> Option<Prefer> {
+        let mut comments = self.leading_comments(span.lo);
+        if let Some(first_item) = first_item.filter(|first_item| first_item.lo != span.lo) {
+            comments.extend(self.leading_comments(first_item.lo));
+        }
+        let pragmas = parse_file_pragmas(&comments);
+        if pragmas.disabled {
+            return None;
+        }
+
+        Some(pragmas.prefer.unwrap_or(self.config.prefer))
+    }
+
+    // 记录子节点中 `{/* jsx-css-modules-ignore-next-line */}` 之后的第一个元素
+    fn mark_ignored_children(&mut self, children: &[JSXElementChild]) {
+        let mut ignore_next = false;
+        for child in children {
+            match child {
+                JSXElementChild::JSXExprContainer(JSXExprContainer {
+                    span,
+                    expr: JSXExpr::JSXEmptyExpr(_),
+                }) => {
+                    // 注释挂在左花括号之后
+                    let pos = span.lo + BytePos(1);
+                    let mut comments = self.leading_comments(pos);
+                    comments.extend(self.trailing_comments(pos));
+                    ignore_next = has_ignore_next_line(&comments);
+                }
+                JSXElementChild::JSXText(text) if text.value.trim().is_empty() => {}
+                JSXElementChild::JSXElement(element) if ignore_next => {
+                    self.ignored_elements.insert(element.span.lo);
+                    ignore_next = false;
+                }
+                _ => ignore_next = false,
+            }
+        }
+    }
+}
+
+impl VisitMut for JsxCssModulesVisitor {
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        let first_item = module.body.first().map(|item| item.span());
+        let Some(prefer) = self.file_prefer(module.span, first_item) else {
+            return;
+        };
+        self.prefer = prefer;
+        self.transform_module(module);
+        if let Some(usage) = self.usage.take() {
+            match self.config.usage_manifest {
+                UsageManifest::Export => module.body.push(usage.to_export_item()),
+                _ => self.add_usage_comment(module.span, &usage),
+            }
+        }
+    }
+
+    fn visit_mut_script(&mut self, script: &mut Script) {
+        let first_item = script.body.first().map(|stmt| stmt.span());
+        let Some(prefer) = self.file_prefer(script.span, first_item) else {
+            return;
+        };
+        self.prefer = prefer;
+        self.transform_script(script);
+        // 脚本文件无法导出，统一写入注释
+        if let Some(usage) = self.usage.take() {
+            self.add_usage_comment(script.span, &usage);
+        }
+    }
+
+    fn visit_mut_jsx_fragment(&mut self, fragment: &mut JSXFragment) {
+        self.mark_ignored_children(&fragment.children);
+        fragment.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_jsx_element(&mut self, jsx: &mut JSXElement) {
+        let ignored = self.ignored_elements.remove(&jsx.span.lo)
+            || has_ignore_next_line(&self.leading_comments(jsx.span.lo));
+
+        // 先处理属性值、展开属性与子节点中嵌套的 JSX
+        self.mark_ignored_children(&jsx.children);
+        jsx.visit_mut_children_with(self);
+
+        if self.matcher_ident.is_none() || ignored || !self.is_element_included(&jsx.opening.name) {
+            return;
+        }
+        if self.config.style_name {
+            self.transform_style_name(&mut jsx.opening.attrs);
+        }
+        let is_component = is_component(&jsx.opening.name);
+        for attr in &mut jsx.opening.attrs {
+            // styleName 模式下 className 总是全局类名，与 babel-plugin-react-css-modules 一致
+            if self.config.style_name && is_attr_named(attr, "className") {
+                continue;
+            }
+            let JSXAttrOrSpread::JSXAttr(attr) = attr else {
+                continue;
+            };
+            let Some(class_attribute) = self.class_attribute(&attr.name, is_component) else {
+                continue;
+            };
+            self.class_attribute = class_attribute;
+            match &mut attr.value {
+                // 处理字符串字面量
+                Some(JSXAttrValue::Lit(Lit::Str(str_lit))) => {
+                    let span = str_lit.span;
+                    attr.value = Some(create_attr_value(
+                        self.transform_class_name_literal(str_lit, attr.span, ClassNameSite::Attribute, self.prefer),
+                        span,
+                    ));
+                }
+                // 处理 JSX 表达式容器（模板字符串、条件表达式和函数调用等）
+                Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                })) => {
+                    *expr = self.transform_class_name_expr(expr.take(), ClassNameSite::Attribute, self.prefer);
+                }
+                _ => {}
+            }
+        }
     }
 }